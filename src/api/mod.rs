@@ -0,0 +1,778 @@
+//! A JSON API over HTTP and WebSocket for web clients, enabled by the `server` feature.
+//!
+//! The game state is sent with the same serde representations as the rest of the crate: a player's view of the game
+//! is a [`GameView`], whose cards, spices and caravans are [`ActionCard`](crate::cards::ActionCard),
+//! [`PointsCard`](crate::cards::PointsCard), [`SpiceAmount`](crate::spice::SpiceAmount) and
+//! [`Caravan`](crate::player::Caravan) values, and actions are [`PlayerAction`] values.
+//!
+//! | Request                         | Body              | Response                           |
+//! |---------------------------------|-------------------|------------------------------------|
+//! | `POST /games`                   | [`CreateGame`]    | `201` [`GameInfo`]                 |
+//! | `GET /games/<id>`               |                   | `200` [`GameInfo`]                 |
+//! | `POST /games/<id>/players`      | [`JoinGame`]      | `201` [`Joined`]                   |
+//! | `GET /games/<id>/state`         |                   | `200` [`GameState`]                |
+//! | `POST /games/<id>/actions`      | [`SubmitAction`]  | `200` [`GameState`]                |
+//! | `GET /games/<id>/events`        |                   | WebSocket of [`GameState`] values  |
+//!
+//! Joining a game returns a token for the seat taken, which the requests about that seat must carry, either as an
+//! `Authorization: Bearer <token>` header or, for browsers opening a WebSocket, as a `token` query parameter. A
+//! client can thus only see its own view of the game and only act for its own seat. The WebSocket sends the state
+//! when it opens and after every change; actions are submitted over HTTP.
+//!
+//! Errors are returned as an [`ApiError`] with the HTTP status: `400` for a malformed request or an illegal action,
+//! `401` for a missing or unknown token, `403` when it is not the player's turn, `404` for an unknown game or path,
+//! `409` when the game is full, has not started or is over, `413` for a body over 64 KiB, and `503` when the server
+//! already hosts [`MAX_GAMES`] games. Every response allows cross-origin requests.
+//!
+//! Games are forgotten [`FINISHED_TIMEOUT`] after they end, or once no player has joined or moved in them for
+//! [`IDLE_TIMEOUT`], closing their WebSockets.
+
+use crate::errors::GameErrors;
+use crate::game::{scoring::Standing, view::GameView, Game};
+use crate::player::PlayerAction;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::io::Read;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+use tungstenite::{handshake::derive_accept_key, protocol::Role, Message, WebSocket};
+
+/// Port the API listens on, unless given another one.
+pub const DEFAULT_API_PORT: u16 = 8080;
+
+/// Largest number of games hosted at once.
+pub const MAX_GAMES: usize = 1000;
+
+/// How long a game is kept without anyone joining or moving in it.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// How long a game is kept after it ends, for its players to fetch the final standings.
+pub const FINISHED_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Largest request body read, in bytes.
+const MAX_BODY: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The body of a request creating a game.
+pub struct CreateGame {
+    pub players: usize,
+    /// Seed to set the game up from, or none for a random one. The seed of a game is never revealed, since it gives
+    /// away the order of the decks.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// What anyone can see of a game, without joining it.
+pub struct GameInfo {
+    /// Identifier of the game in the request paths.
+    pub game: u64,
+    pub players: usize,
+    /// Names of the players who have joined, by seat.
+    pub names: Vec<String>,
+    pub over: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The body of a request joining a game.
+pub struct JoinGame {
+    /// Name shown to the other players, `Player <seat>` if blank.
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The seat given to a player joining a game.
+pub struct Joined {
+    /// Seat of the player, starting at 0.
+    pub seat: usize,
+    /// Token authenticating the requests for this seat.
+    pub token: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The body of a request playing an action.
+pub struct SubmitAction {
+    pub action: PlayerAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// A game as one of its players observes it.
+pub struct GameState {
+    pub game: u64,
+    /// Seat of the player observing the game.
+    pub seat: usize,
+    /// Names of the players who have joined, by seat.
+    pub names: Vec<String>,
+    /// Whether every seat is taken, after which actions can be played.
+    pub started: bool,
+    pub over: bool,
+    /// The player's view, with the legal actions when it is their turn, see [`Game::view_for`].
+    pub view: GameView,
+    /// The final standings, once the game is over.
+    pub standings: Option<Vec<Standing>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The body of an error response.
+pub struct ApiError {
+    pub error: String,
+}
+
+/// An error response, with its HTTP status.
+struct Failure {
+    status: u16,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: u16, message: impl ToString) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A successful response, with its HTTP status and JSON body.
+type Reply = Result<(u16, String), Failure>;
+
+/// Serializes a response body.
+fn json<T: Serialize>(status: u16, body: &T) -> Reply {
+    serde_json::to_string(body)
+        .map(|body| (status, body))
+        .map_err(|error| Failure::new(500, error))
+}
+
+/// A game hosted by the API.
+struct HostedGame {
+    game: Game,
+    names: Vec<String>,
+    tokens: Vec<String>,
+    /// The seat and channel of every open WebSocket, which is sent the serialized state after every change.
+    subscribers: Vec<(usize, Sender<String>)>,
+    /// When a player last joined or moved.
+    last_active: Instant,
+}
+
+impl HostedGame {
+    /// Check if the game has been left alone long enough to be forgotten, see [`IDLE_TIMEOUT`] and
+    /// [`FINISHED_TIMEOUT`].
+    fn is_expired(&self, now: Instant) -> bool {
+        let timeout = match self.game.is_over() {
+            true => FINISHED_TIMEOUT,
+            false => IDLE_TIMEOUT,
+        };
+        now.saturating_duration_since(self.last_active) >= timeout
+    }
+}
+
+/// The games hosted by an [`ApiServer`].
+#[derive(Default)]
+struct Lobby {
+    games: HashMap<u64, HostedGame>,
+    next_game: u64,
+}
+
+impl Lobby {
+    /// Answers a request with the body read from it, or returns the subscription of a WebSocket request.
+    fn route(
+        &mut self,
+        method: Method,
+        segments: &[&str],
+        token: Option<&str>,
+        body: String,
+    ) -> Result<Routed, Failure> {
+        self.evict(Instant::now());
+        let reply = match (method, segments) {
+            (Method::Post, ["games"]) => self.create(body),
+            (Method::Get, ["games", id]) => self.info(id),
+            (Method::Post, ["games", id, "players"]) => self.join(id, body),
+            (Method::Get, ["games", id, "state"]) => self
+                .authorize(id, token)
+                .and_then(|(id, seat)| json(200, &self.state(id, seat)?)),
+            (Method::Post, ["games", id, "actions"]) => self.act(id, token, body),
+            (Method::Get, ["games", id, "events"]) => {
+                return self.subscribe(id, token).map(Routed::Subscribe)
+            }
+            _ => Err(Failure::new(404, "Not found")),
+        };
+        reply.map(Routed::Reply)
+    }
+
+    /// Finds a game by its identifier in a path.
+    fn game(&self, id: &str) -> Result<(u64, &HostedGame), Failure> {
+        id.parse()
+            .ok()
+            .and_then(|id| Some((id, self.games.get(&id)?)))
+            .ok_or_else(|| Failure::new(404, "No such game"))
+    }
+
+    /// Finds a game and the seat of a token.
+    fn authorize(&self, id: &str, token: Option<&str>) -> Result<(u64, usize), Failure> {
+        let (id, hosted) = self.game(id)?;
+        let token = token.ok_or_else(|| Failure::new(401, "Missing token"))?;
+        let seat = hosted
+            .tokens
+            .iter()
+            .position(|x| tokens_match(x, token))
+            .ok_or_else(|| Failure::new(401, "Unknown token"))?;
+        Ok((id, seat))
+    }
+
+    /// Get a game as a player observes it.
+    fn state(&self, id: u64, seat: usize) -> Result<GameState, Failure> {
+        let hosted = self
+            .games
+            .get(&id)
+            .ok_or_else(|| Failure::new(404, "No such game"))?;
+        let over = hosted.game.is_over();
+        Ok(GameState {
+            game: id,
+            seat,
+            names: hosted.names.clone(),
+            started: hosted.names.len() == hosted.game.get_players().len(),
+            over,
+            view: hosted.game.view_for(seat),
+            standings: over.then(|| hosted.game.standings()),
+        })
+    }
+
+    fn create(&mut self, body: String) -> Reply {
+        let request: CreateGame =
+            serde_json::from_str(&body).map_err(|error| Failure::new(400, error))?;
+        if self.games.len() >= MAX_GAMES {
+            return Err(Failure::new(503, "Too many games, try again later"));
+        }
+        let seed = match request.seed {
+            Some(seed) => seed,
+            None => u64::from_le_bytes(random_bytes()?),
+        };
+        let game = Game::new(request.players, seed).map_err(|error| Failure::new(400, error))?;
+        self.next_game += 1;
+        let id = self.next_game;
+        self.games.insert(
+            id,
+            HostedGame {
+                game,
+                names: Vec::new(),
+                tokens: Vec::new(),
+                subscribers: Vec::new(),
+                last_active: Instant::now(),
+            },
+        );
+        self.info(&id.to_string()).map(|(_, body)| (201, body))
+    }
+
+    fn info(&self, id: &str) -> Reply {
+        let (id, hosted) = self.game(id)?;
+        json(
+            200,
+            &GameInfo {
+                game: id,
+                players: hosted.game.get_players().len(),
+                names: hosted.names.clone(),
+                over: hosted.game.is_over(),
+            },
+        )
+    }
+
+    fn join(&mut self, id: &str, body: String) -> Reply {
+        let request: JoinGame =
+            serde_json::from_str(&body).map_err(|error| Failure::new(400, error))?;
+        let (id, _) = self.game(id)?;
+        let hosted = self
+            .games
+            .get_mut(&id)
+            .ok_or_else(|| Failure::new(404, "No such game"))?;
+        let seat = hosted.names.len();
+        if seat == hosted.game.get_players().len() {
+            return Err(Failure::new(409, "The game is full"));
+        }
+        let name = match request.name.trim() {
+            "" => format!("Player {}", seat + 1),
+            name => name.to_string(),
+        };
+        let token: String = random_bytes::<16>()?
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        hosted.names.push(name);
+        hosted.tokens.push(token.clone());
+        hosted.last_active = Instant::now();
+        self.notify(id);
+        json(201, &Joined { seat, token })
+    }
+
+    fn act(&mut self, id: &str, token: Option<&str>, body: String) -> Reply {
+        let (id, seat) = self.authorize(id, token)?;
+        let request: SubmitAction =
+            serde_json::from_str(&body).map_err(|error| Failure::new(400, error))?;
+        let hosted = self
+            .games
+            .get_mut(&id)
+            .ok_or_else(|| Failure::new(404, "No such game"))?;
+        if hosted.names.len() < hosted.game.get_players().len() {
+            return Err(Failure::new(409, "Waiting for players to join"));
+        }
+        if hosted.game.is_over() {
+            return Err(Failure::new(409, GameErrors::GameOver));
+        }
+        if seat != hosted.game.get_current_player_index() {
+            return Err(Failure::new(403, "It is not your turn"));
+        }
+        hosted
+            .game
+            .check_limits(&request.action)
+            .map_err(|error| Failure::new(400, error))?;
+        hosted
+            .game
+            .play(request.action)
+            .map_err(|error| Failure::new(400, error))?;
+        hosted.last_active = Instant::now();
+        self.notify(id);
+        json(200, &self.state(id, seat)?)
+    }
+
+    /// Opens a subscription to a game's states, starting with the current one.
+    fn subscribe(&mut self, id: &str, token: Option<&str>) -> Result<Receiver<String>, Failure> {
+        let (id, seat) = self.authorize(id, token)?;
+        let (sender, states) = mpsc::channel();
+        let (_, state) = json(200, &self.state(id, seat)?)?;
+        sender.send(state).ok();
+        if let Some(hosted) = self.games.get_mut(&id) {
+            if !hosted.game.is_over() {
+                hosted.subscribers.push((seat, sender));
+            }
+        }
+        Ok(states)
+    }
+
+    /// Forgets the games that have expired by the given time, closing their subscriptions.
+    fn evict(&mut self, now: Instant) {
+        self.games.retain(|_, hosted| !hosted.is_expired(now));
+    }
+
+    /// Sends the new state of a game to its subscribers, and closes the subscriptions once it is over.
+    fn notify(&mut self, id: u64) {
+        let Some(subscribers) = self
+            .games
+            .get_mut(&id)
+            .map(|hosted| std::mem::take(&mut hosted.subscribers))
+        else {
+            return;
+        };
+        let subscribers: Vec<_> = subscribers
+            .into_iter()
+            .filter(|(seat, sender)| {
+                let state = self.state(id, *seat).and_then(|state| json(200, &state));
+                state.is_ok_and(|(_, state)| sender.send(state).is_ok())
+            })
+            .collect();
+        if let Some(hosted) = self.games.get_mut(&id) {
+            if !hosted.game.is_over() {
+                hosted.subscribers = subscribers;
+            }
+        }
+    }
+}
+
+/// What a request routed by a [`Lobby`] turns into.
+enum Routed {
+    Reply((u16, String)),
+    Subscribe(Receiver<String>),
+}
+
+/// Answers a request. The body is read before locking the lobby, so that a slow client only holds up its own request.
+fn handle(lobby: &Mutex<Lobby>, mut request: Request) {
+    if *request.method() == Method::Options {
+        // Preflight of a cross-origin request.
+        request.respond(with_headers(Response::empty(204))).ok();
+        return;
+    }
+
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+    let token = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().strip_prefix("Bearer "))
+        .or_else(|| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("token="))
+        })
+        .map(|token| token.trim().to_string());
+
+    let method = request.method().clone();
+    let body = match method {
+        Method::Post => read_body(&mut request),
+        _ => Ok(String::new()),
+    };
+    let routed = body.and_then(|body| {
+        // A panic while answering another request must not take every game down with it.
+        let mut lobby = lobby.lock().unwrap_or_else(PoisonError::into_inner);
+        lobby.route(method, &segments, token.as_deref(), body)
+    });
+
+    let (status, body) = match routed {
+        Ok(Routed::Reply(reply)) => reply,
+        Ok(Routed::Subscribe(states)) => return upgrade(request, states),
+        Err(failure) => {
+            let error = ApiError {
+                error: failure.message,
+            };
+            let body = serde_json::to_string(&error).unwrap_or_default();
+            (failure.status, body)
+        }
+    };
+    let response = Response::from_string(body).with_status_code(status);
+    let response = with_headers(response);
+    let response = match Header::from_bytes("Content-Type", "application/json") {
+        Ok(header) => response.with_header(header),
+        Err(()) => response,
+    };
+    request.respond(response).ok();
+}
+
+/// Reads the body of a request, refusing those over [`MAX_BODY`].
+fn read_body(request: &mut Request) -> Result<String, Failure> {
+    let too_large = || Failure::new(413, format!("The body is over {MAX_BODY} bytes"));
+    if request
+        .body_length()
+        .is_some_and(|length| length as u64 > MAX_BODY)
+    {
+        return Err(too_large());
+    }
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)
+        .map_err(|error| Failure::new(400, error))?;
+    if body.len() as u64 > MAX_BODY {
+        return Err(too_large());
+    }
+    Ok(body)
+}
+
+/// Adds the headers allowing cross-origin requests to a response.
+fn with_headers<R: Read>(response: Response<R>) -> Response<R> {
+    [
+        ("Access-Control-Allow-Origin", "*"),
+        ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+        (
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| Header::from_bytes(field, value).ok())
+    .fold(response, Response::with_header)
+}
+
+/// Turns a request into a WebSocket, and sends it the states of a subscription until the game is over or the client
+/// goes away.
+fn upgrade(request: Request, states: Receiver<String>) {
+    let key = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Sec-WebSocket-Key"))
+        .map(|header| derive_accept_key(header.value.as_bytes()));
+    let accept = key.and_then(|key| Header::from_bytes("Sec-WebSocket-Accept", key).ok());
+    let Some(accept) = accept else {
+        let body = serde_json::to_string(&ApiError {
+            error: "Expected a WebSocket handshake".to_string(),
+        })
+        .unwrap_or_default();
+        request
+            .respond(with_headers(
+                Response::from_string(body).with_status_code(426),
+            ))
+            .ok();
+        return;
+    };
+
+    let response = Response::empty(StatusCode(101)).with_header(accept);
+    let stream = request.upgrade("websocket", response);
+    thread::spawn(move || {
+        let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+        for state in states {
+            if socket.send(Message::text(state)).is_err() {
+                return;
+            }
+        }
+        socket.close(None).ok();
+        socket.flush().ok();
+    });
+}
+
+/// Get random bytes from the operating system, for seeds and tokens.
+fn random_bytes<const N: usize>() -> Result<[u8; N], Failure> {
+    let mut bytes = [0; N];
+    getrandom::getrandom(&mut bytes).map_err(|error| Failure::new(500, error))?;
+    Ok(bytes)
+}
+
+/// Compares a token with a guess in a time that does not depend on how much of the guess is right.
+fn tokens_match(token: &str, guess: &str) -> bool {
+    let difference = token
+        .bytes()
+        .zip(guess.bytes())
+        .fold(0, |difference, (x, y)| black_box(difference | (x ^ y)));
+    token.len() == guess.len() && difference == 0
+}
+
+/// Serves the JSON API, see the [module documentation](self).
+pub struct ApiServer {
+    http: tiny_http::Server,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+impl ApiServer {
+    /// Listens for requests on an address, such as `0.0.0.0:8080` for every network interface or `127.0.0.1:0` for a
+    /// free port on this machine only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NetworkIo` if the address cannot be listened on.
+    pub fn bind(address: impl ToSocketAddrs) -> Result<Self, GameErrors> {
+        let http = tiny_http::Server::http(address)
+            .map_err(|error| GameErrors::NetworkIo(error.to_string()))?;
+        Ok(Self {
+            http,
+            lobby: Arc::default(),
+        })
+    }
+
+    /// Get the address the API listens on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NetworkIo` if the server is not listening on an IP address.
+    pub fn local_addr(&self) -> Result<SocketAddr, GameErrors> {
+        self.http
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| GameErrors::NetworkIo("not an IP address".to_string()))
+    }
+
+    /// Answers requests until the process ends. Each request is answered by its own thread, as is each WebSocket;
+    /// the threads take turns with the games.
+    pub fn run(self) {
+        for request in self.http.incoming_requests() {
+            let lobby = Arc::clone(&self.lobby);
+            thread::spawn(move || handle(&lobby, request));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{tokens_match, ApiServer, GameState, Joined, Lobby, IDLE_TIMEOUT, MAX_GAMES};
+    use crate::bots::{random::RandomBot, Bot};
+    use crate::errors::GameErrors;
+    use crate::game::Game;
+    use crate::player::PlayerAction;
+    use serde_json::{json, Value};
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+    use std::thread;
+    use std::time::Instant;
+
+    fn start() -> SocketAddr {
+        let server = ApiServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        address
+    }
+
+    /// Sends a request and returns the status and JSON body of the response.
+    fn request(
+        address: SocketAddr,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: Value,
+    ) -> (u16, Value) {
+        let body = body.to_string();
+        let auth = token.map_or(String::new(), |token| {
+            format!("Authorization: Bearer {token}\r\n")
+        });
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{auth}\
+             Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn create_and_join(address: SocketAddr, names: &[&str]) -> Vec<Joined> {
+        let body = json!({ "players": 2, "seed": 4 });
+        assert_eq!(request(address, "POST", "/games", None, body).0, 201);
+        names
+            .iter()
+            .map(|name| {
+                let body = json!({ "name": name });
+                let (status, joined) = request(address, "POST", "/games/1/players", None, body);
+                assert_eq!(status, 201);
+                serde_json::from_value(joined).unwrap()
+            })
+            .collect()
+    }
+
+    fn play(address: SocketAddr, token: &str, action: &PlayerAction) -> (u16, Value) {
+        let body = json!({ "action": action });
+        request(address, "POST", "/games/1/actions", Some(token), body)
+    }
+
+    #[test]
+    fn test_http_game() {
+        let address = start();
+        let players = create_and_join(address, &["ann", ""]);
+        assert_eq!(players[1].seat, 1);
+        assert_eq!(players[0].token.len(), 32);
+        assert_ne!(players[0].token, players[1].token);
+        assert!(tokens_match(&players[0].token, &players[0].token.clone()));
+        assert!(!tokens_match(&players[0].token, &players[0].token[..31]));
+        let (status, info) = request(address, "GET", "/games/1", None, Value::Null);
+        assert_eq!(status, 200);
+        assert_eq!(
+            info,
+            json!({ "game": 1, "players": 2, "names": ["ann", "Player 2"], "over": false })
+        );
+        let error = |message: &str| json!({ "error": message });
+        let body = json!({ "name": "cy" });
+        let full = request(address, "POST", "/games/1/players", None, body);
+        assert_eq!(full, (409, error("The game is full")));
+
+        let state = |token| request(address, "GET", "/games/1/state", token, Value::Null);
+        assert_eq!(state(None), (401, error("Missing token")));
+        assert_eq!(state(Some("guess")), (401, error("Unknown token")));
+        let bo: GameState = serde_json::from_value(state(Some(&players[1].token)).1).unwrap();
+        assert!(bo.started && bo.view.legal_actions.is_empty());
+        assert_eq!(
+            play(address, &players[1].token, &PlayerAction::Rest),
+            (403, error("It is not your turn"))
+        );
+        assert_eq!(
+            play(address, &players[0].token, &PlayerAction::Rest),
+            (400, error(&GameErrors::EmptyDiscardPile.to_string()))
+        );
+        let payment = json!({ "turmeric": 200, "saffron": 57, "cardamon": 0, "cinnamon": 0 });
+        let body = json!({ "action": { "acquire_card": [{ "gain": payment }, payment] } });
+        assert_eq!(
+            request(
+                address,
+                "POST",
+                "/games/1/actions",
+                Some(&players[0].token),
+                body
+            ),
+            (400, error(&GameErrors::TooManySpices(5).to_string()))
+        );
+        let missing = request(address, "GET", "/games/9/state", None, Value::Null);
+        assert_eq!(missing, (404, error("No such game")));
+
+        let mut local = Game::new(2, 4).unwrap();
+        let mut bot = RandomBot::new(4);
+        for _ in 0..20 {
+            let seat = local.get_current_player_index();
+            let action = bot.choose_action(&local.view()).unwrap();
+            let (status, body) = play(address, &players[seat].token, &action);
+            local.play(action).unwrap();
+            assert_eq!(status, 200);
+            let state: GameState = serde_json::from_value(body).unwrap();
+            assert_eq!(state.view, local.view_for(seat));
+        }
+    }
+
+    #[test]
+    fn test_request_bodies() {
+        let address = start();
+        let send_head = |length: usize| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "POST /games HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+                 Content-Length: {length}\r\n\r\n"
+            )
+            .unwrap();
+            stream
+        };
+
+        let mut response = String::new();
+        send_head(100_000).read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+
+        // A client that never sends its body does not hold up the others.
+        let stalled = send_head(20);
+        let body = json!({ "players": 2 });
+        assert_eq!(request(address, "POST", "/games", None, body).0, 201);
+        drop(stalled);
+    }
+
+    #[test]
+    fn test_websocket_subscription() {
+        let address = start();
+        let players = create_and_join(address, &["ann"]);
+        let url = format!("ws://{address}/games/1/events");
+        assert!(tungstenite::connect(&url).is_err());
+        let (mut socket, _) =
+            tungstenite::connect(format!("{url}?token={}", players[0].token)).unwrap();
+        let mut next_state = || -> GameState {
+            let message = socket.read().unwrap();
+            serde_json::from_str(message.to_text().unwrap()).unwrap()
+        };
+        assert!(!next_state().started);
+
+        let body = json!({ "name": "bo" });
+        assert_eq!(
+            request(address, "POST", "/games/1/players", None, body).0,
+            201
+        );
+        let state = next_state();
+        assert!(state.started);
+        assert_eq!(state.names, ["ann", "bo"]);
+
+        let action: PlayerAction = "play YY".parse().unwrap();
+        assert_eq!(play(address, &players[0].token, &action).0, 200);
+        assert_eq!(next_state().view.history, [action]);
+    }
+
+    #[test]
+    fn test_lobby_limits_games() {
+        let mut lobby = Lobby::default();
+        let create = |lobby: &mut Lobby| {
+            let body = json!({ "players": 2 }).to_string();
+            lobby
+                .create(body)
+                .map(|(status, _)| status)
+                .map_err(|failure| failure.status)
+        };
+        for _ in 0..MAX_GAMES {
+            assert_eq!(create(&mut lobby), Ok(201));
+        }
+        assert_eq!(create(&mut lobby), Err(503));
+
+        lobby.evict(Instant::now());
+        assert_eq!(lobby.games.len(), MAX_GAMES);
+        lobby.evict(Instant::now() + IDLE_TIMEOUT);
+        assert!(lobby.games.is_empty());
+        assert_eq!(create(&mut lobby), Ok(201));
+    }
+}
@@ -0,0 +1,468 @@
+//! Bots running as external programs, written in any language, that talk to the game over their standard input and
+//! output.
+//!
+//! # Protocol
+//!
+//! The protocol is line based, in the card notation of [`ActionCard`](crate::cards::ActionCard),
+//! [`PointsCard`](crate::cards::PointsCard) and [`PlayerAction`]. Lines sent to the bot end with `\n`, and the bot's
+//! replies may end with `\n` or `\r\n`. Anything the bot writes to its standard error is passed through, for
+//! debugging.
+//!
+//! When the bot starts, it is sent `csr <version>` with the version of the protocol, [`PROTOCOL_VERSION`]. It
+//! replies with an optional `name <name>` line and then `ready`.
+//!
+//! Each time the bot is to move, it is sent the position as the player to move can observe it, one line per item,
+//! ending with the legal moves and the time limit:
+//!
+//! ```text
+//! position
+//! seat 2 3
+//! points 12:RRGG/gold 8:YYGG/silver 13:YYRRGG 9:YYYGG 14:RRRBB
+//! coins 6 6
+//! merchant YYY>GG U3/Y YR RR>GGG R>YY YYY
+//! decks 30 36
+//! player 1 caravan YYYR score 0 hand YY,U2,G discard - scored - coins -
+//! player 2 caravan YYYY score 0 hand YY,U2 discard - scored - coins -
+//! player 3 caravan YYYY score 0 hand YY,U2 discard - scored - coins -
+//! last_round no
+//! moves 9
+//! play YY
+//! play U2 Y+1 Y+1
+//! ...
+//! acquire YR YY
+//! go 5000
+//! ```
+//!
+//! * `seat` gives the seat of the player to move and the number of players, seats starting at 1.
+//! * `points` lists the points row, with the coin above a card after a `/`, and `coins` the gold and silver coins
+//!   left.
+//! * `merchant` lists the merchant row, with the spices left on a card after a `/`.
+//! * `decks` gives the number of points and merchant cards left in the decks.
+//! * Each `player` line lists a player's hand, discard pile, score pile and coins separated by commas, or `-` if
+//!   there are none.
+//! * `last_round` is `yes` once the end of the game has been triggered.
+//! * `moves` gives the number of legal moves, listed one per line after it, and `go` the time limit in milliseconds.
+//!
+//! The bot replies with `move <move>`, either in card notation or as the number of a move in the list, the first
+//! move being `move 1`. It may send `info <text>` lines before its move, which are ignored. When the game is over
+//! the bot is sent `quit`, and it is stopped if it has not exited shortly after.
+//!
+//! A bot that cannot be started, exits, does not reply within the time limit, or replies with anything else
+//! forfeits the game, see [`GameErrors::BotForfeit`].
+
+use super::Bot;
+use crate::errors::GameErrors;
+use crate::game::view::GameView;
+use crate::player::PlayerAction;
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Version of the protocol, sent to the bot when it starts.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Time a bot has to start and to choose each move, unless set otherwise.
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+/// Time a bot has to exit after being sent `quit`, before it is killed.
+const QUIT_GRACE: Duration = Duration::from_millis(200);
+
+#[derive(Debug)]
+/// A running bot program, with the lines it wrote that have not been read yet.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl Process {
+    /// Stops the program, giving it `grace` to exit on its own after `quit` before killing it.
+    fn stop(mut self, grace: Duration) {
+        writeln!(self.stdin, "quit").ok();
+        drop(self.stdin);
+        let start = Instant::now();
+        while start.elapsed() < grace {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+#[derive(Debug)]
+/// A bot played by an external program, see the [module documentation](self) for the protocol.
+///
+/// A bot that breaks the protocol forfeits: it is stopped, and every later call to
+/// [`choose_action`](Bot::choose_action) returns the same `GameErrors::BotForfeit`.
+pub struct ExternalBot {
+    name: String,
+    time_limit: Duration,
+    process: Option<Process>,
+    forfeit: Option<String>,
+}
+
+impl ExternalBot {
+    /// Starts a bot program with its arguments, and waits for it to be ready.
+    ///
+    /// The bot is named after the program until it gives its own name. If the program cannot be started or does not
+    /// get ready in time, the bot forfeits its first move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::bots::{external::{ExternalBot, DEFAULT_TIME_LIMIT}, Bot};
+    /// let bot = ExternalBot::new("sh", &["-c", "read -r hello; echo name echo; echo ready"], DEFAULT_TIME_LIMIT);
+    /// assert_eq!(bot.name(), "echo");
+    /// assert_eq!(bot.get_forfeit(), None);
+    /// ```
+    pub fn new(program: &str, args: &[&str], time_limit: Duration) -> Self {
+        let mut bot = Self {
+            name: Path::new(program)
+                .file_name()
+                .map_or(program.to_string(), |name| {
+                    name.to_string_lossy().into_owned()
+                }),
+            time_limit,
+            process: None,
+            forfeit: None,
+        };
+        if let Err(reason) = bot.start(program, args) {
+            bot.forfeit(reason);
+        }
+        bot
+    }
+
+    /// Get the time the bot has to choose each move.
+    pub fn get_time_limit(&self) -> Duration {
+        self.time_limit
+    }
+
+    /// Get the reason the bot forfeited, if it did.
+    pub fn get_forfeit(&self) -> Option<&str> {
+        self.forfeit.as_deref()
+    }
+
+    /// Starts the program and reads its reply to the greeting, up to `ready`.
+    fn start(&mut self, program: &str, args: &[&str]) -> Result<(), String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| format!("cannot start {program:?}: {error}"))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(format!("cannot connect to {program:?}"));
+        };
+
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        self.process = Some(Process {
+            child,
+            stdin,
+            lines,
+        });
+
+        self.send(format_args!("csr {PROTOCOL_VERSION}"))?;
+        let deadline = Instant::now() + self.time_limit;
+        loop {
+            let line = self.receive(deadline, "get ready")?;
+            match line.split_once(' ').unwrap_or((&line, "")) {
+                ("ready", "") => return Ok(()),
+                ("name", name) if !name.trim().is_empty() => self.name = name.trim().to_string(),
+                ("info", _) => {}
+                _ => return Err(format!("replied {line:?} instead of \"ready\"")),
+            }
+        }
+    }
+
+    /// Writes a line to the program.
+    fn send(&mut self, line: impl Display) -> Result<(), String> {
+        let process = self.process.as_mut().ok_or("is not running")?;
+        match writeln!(process.stdin, "{line}").and_then(|()| process.stdin.flush()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(self.exit_reason()),
+        }
+    }
+
+    /// Reads the next line from the program, waiting until the deadline at most. Blank lines are skipped.
+    fn receive(&mut self, deadline: Instant, task: &str) -> Result<String, String> {
+        let process = self.process.as_mut().ok_or("is not running")?;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match process.lines.recv_timeout(timeout) {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => return Ok(line.trim_end().to_string()),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!(
+                        "did not {task} within {} ms",
+                        self.time_limit.as_millis()
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err(self.exit_reason()),
+            }
+        }
+    }
+
+    /// Describes how the program stopped talking, with its exit status if it has exited.
+    fn exit_reason(&mut self) -> String {
+        let Some(process) = self.process.as_mut() else {
+            return "is not running".to_string();
+        };
+        // Give the program a moment to exit after closing its output.
+        let start = Instant::now();
+        while start.elapsed() < QUIT_GRACE {
+            if let Ok(Some(status)) = process.child.try_wait() {
+                return format!("exited unexpectedly ({status})");
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        "closed its input or output".to_string()
+    }
+
+    /// Records a forfeit and stops the program.
+    fn forfeit(&mut self, reason: String) -> GameErrors {
+        if let Some(process) = self.process.take() {
+            process.stop(Duration::ZERO);
+        }
+        self.forfeit = Some(reason.clone());
+        GameErrors::BotForfeit(self.name.clone(), reason)
+    }
+
+    /// Sends the position and reads back a legal move.
+    fn request_move(&mut self, view: &GameView) -> Result<PlayerAction, String> {
+        self.send(position(view, self.time_limit))?;
+        let deadline = Instant::now() + self.time_limit;
+        loop {
+            let line = self.receive(deadline, "move")?;
+            let reply = match line.split_once(' ').unwrap_or((&line, "")) {
+                ("info", _) => continue,
+                ("move", reply) => reply.trim(),
+                _ => return Err(format!("replied {line:?} instead of a move")),
+            };
+
+            let action = match reply.parse::<usize>() {
+                Ok(number) => view
+                    .legal_actions
+                    .get(number.wrapping_sub(1))
+                    .cloned()
+                    .ok_or_else(|| {
+                        format!(
+                            "replied {line:?}, but there are only {} moves",
+                            view.legal_actions.len()
+                        )
+                    })?,
+                Err(_) => reply
+                    .parse::<PlayerAction>()
+                    .map_err(|error| format!("replied {line:?}: {error}"))?,
+            };
+            if !view.legal_actions.contains(&action) {
+                return Err(format!("replied {line:?}, which is not a legal move"));
+            }
+            return Ok(action);
+        }
+    }
+}
+
+impl Bot for ExternalBot {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Sends the position to the program and reads back its move.
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::NoLegalActions` if there is nothing to play.
+    /// * `GameErrors::BotForfeit` if the program has exited, does not reply in time or replies with anything but a
+    ///   legal move, now or earlier.
+    fn choose_action(&mut self, view: &GameView) -> Result<PlayerAction, GameErrors> {
+        if let Some(reason) = &self.forfeit {
+            return Err(GameErrors::BotForfeit(self.name.clone(), reason.clone()));
+        }
+        if view.legal_actions.is_empty() {
+            return Err(GameErrors::NoLegalActions);
+        }
+        self.request_move(view)
+            .map_err(|reason| self.forfeit(reason))
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        if let Some(process) = self.process.take() {
+            process.stop(QUIT_GRACE);
+        }
+    }
+}
+
+/// Get the message describing a position to a bot, from `position` to `go`, see the
+/// [module documentation](self).
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{bots::external::position, game::Game};
+/// use std::time::Duration;
+/// let message = position(&Game::new(2, 1).unwrap().view(), Duration::from_secs(2));
+/// assert!(message.starts_with("position\nseat 1 2\n"));
+/// assert!(message.contains("\nplayer 1 caravan YYY score 0 hand YY,U2 discard - scored - coins -\n"));
+/// assert!(message.contains("\nmerchant YR>B YYY>RRR YY>G YYYY>RRG G>YRR BB>RGGG\n"));
+/// assert!(message.contains("\nmoves 8\nplay YY\nplay U2 Y+1\n"));
+/// assert!(message.ends_with("\nacquire YYYY>RRG YYY\ngo 2000"));
+/// ```
+pub fn position(view: &GameView, time_limit: Duration) -> String {
+    let join = |items: Vec<String>, separator: &str| match items.is_empty() {
+        true => "-".to_string(),
+        false => items.join(separator),
+    };
+
+    let points = (0..view.points_row.len())
+        .map(|slot| match view.coins.coin_for_slot(slot) {
+            Some(coin) => format!(
+                "{}/{}",
+                view.points_row[slot],
+                format!("{coin:?}").to_lowercase()
+            ),
+            None => view.points_row[slot].to_string(),
+        })
+        .collect();
+    let merchant = view
+        .merchant_row
+        .iter()
+        .map(|slot| match slot.spices.total() {
+            0 => slot.card.to_string(),
+            _ => format!("{}/{}", slot.card, slot.spices),
+        })
+        .collect();
+
+    let mut lines = vec![
+        "position".to_string(),
+        format!("seat {} {}", view.seat + 1, view.players.len()),
+        format!("points {}", join(points, " ")),
+        format!("coins {} {}", view.coins.gold, view.coins.silver),
+        format!("merchant {}", join(merchant, " ")),
+        format!("decks {} {}", view.points_deck_len, view.merchant_deck_len),
+    ];
+    for (idx, player) in view.players.iter().enumerate() {
+        let coins: Vec<_> = player
+            .get_coins()
+            .iter()
+            .map(|coin| format!("{coin:?}").to_lowercase())
+            .collect();
+        lines.push(format!(
+            "player {} caravan {} score {} hand {} discard {} scored {} coins {}",
+            idx + 1,
+            player.get_caravan().get_spice_amount(),
+            player.score(),
+            join(to_strings(player.get_hand()), ","),
+            join(to_strings(player.get_discard_pile()), ","),
+            join(to_strings(player.get_score_pile()), ","),
+            join(coins, ","),
+        ));
+    }
+    lines.push(format!(
+        "last_round {}",
+        if view.end_triggered { "yes" } else { "no" }
+    ));
+    lines.push(format!("moves {}", view.legal_actions.len()));
+    lines.extend(view.legal_actions.iter().map(ToString::to_string));
+    lines.push(format!("go {}", time_limit.as_millis()));
+    lines.join("\n")
+}
+
+/// Get the notation of each card.
+fn to_strings<T: Display>(items: &[T]) -> Vec<String> {
+    items.iter().map(ToString::to_string).collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::bots::external::ExternalBot;
+    use crate::bots::{play_out, random::RandomBot, Bot};
+    use crate::errors::GameErrors;
+    use crate::game::Game;
+    use std::time::Duration;
+
+    /// Starts a bot from a shell script that replies to every `go` with the given line.
+    fn shell_bot(reply: &str) -> ExternalBot {
+        let script = format!(
+            "echo name shell; echo ready; while read -r line; do
+                case \"$line\" in go*) {reply};; quit) exit 0;; esac
+            done"
+        );
+        ExternalBot::new("sh", &["-c", &script], Duration::from_millis(500))
+    }
+
+    #[test]
+    fn test_plays_a_game() {
+        let mut game = Game::new(2, 8).unwrap();
+        let mut bots: Vec<Box<dyn Bot>> = vec![
+            Box::new(shell_bot("echo 'info first move'; echo 'move 1'")),
+            Box::new(RandomBot::new(8)),
+        ];
+        play_out(&mut game, &mut bots, 1000).unwrap();
+        assert!(game.is_over());
+        assert_eq!(bots[0].name(), "shell");
+    }
+
+    #[test]
+    fn test_forfeits() {
+        let view = Game::new(2, 8).unwrap().view();
+        let forfeit = |mut bot: ExternalBot| match bot.choose_action(&view) {
+            Err(GameErrors::BotForfeit(_, reason)) => reason,
+            result => panic!("expected a forfeit, got {result:?}"),
+        };
+
+        assert_eq!(
+            forfeit(shell_bot("echo 'move score 20:BBBBB'")),
+            "replied \"move score 20:BBBBB\", which is not a legal move"
+        );
+        assert_eq!(
+            forfeit(shell_bot("echo 'move 99'")),
+            format!(
+                "replied \"move 99\", but there are only {} moves",
+                view.legal_actions.len()
+            )
+        );
+        assert!(forfeit(shell_bot("echo 'move play QQ'")).starts_with("replied \"move play QQ\": "));
+        assert_eq!(forfeit(shell_bot("sleep 2")), "did not move within 500 ms");
+        assert_eq!(
+            forfeit(shell_bot("exit 3")),
+            "exited unexpectedly (exit status: 3)"
+        );
+        assert!(forfeit(ExternalBot::new(
+            "/nonexistent/bot",
+            &[],
+            Duration::from_secs(1)
+        ))
+        .starts_with("cannot start \"/nonexistent/bot\""));
+
+        // A bot that forfeited keeps forfeiting.
+        let mut bot = shell_bot("echo 'resign'");
+        assert!(bot.choose_action(&view).is_err());
+        assert_eq!(
+            bot.choose_action(&view),
+            Err(GameErrors::BotForfeit(
+                "shell".to_string(),
+                "replied \"resign\" instead of a move".to_string()
+            ))
+        );
+    }
+}
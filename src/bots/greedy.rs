@@ -0,0 +1,279 @@
+use super::Bot;
+use crate::cards::{ActionCard, PointsCard};
+use crate::errors::GameErrors;
+use crate::game::view::GameView;
+use crate::player::{PlayerAction, MAX_CARAVAN_SIZE};
+use crate::spice::SpiceAmount;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The tunable weights of a [`GreedyBot`].
+pub struct GreedyWeights {
+    /// Value of a coin point, relative to a points-card point, when ranking points cards.
+    pub coin: f64,
+    /// Cost of each upgrade step still needed to afford the target points card.
+    pub upgrade_step: f64,
+    /// Cost of each cube still missing to afford the target points card, on top of the steps to upgrade it.
+    pub missing_cube: f64,
+    /// Cost of each cube over the caravan's capacity, which would have to be discarded.
+    pub overflow_cube: f64,
+    /// Smallest decrease in distance to the target for a card play to be preferred over acquiring or resting.
+    pub min_progress: f64,
+    /// Value of each spice level an action card produces when played, when ranking cards to acquire.
+    pub engine: f64,
+    /// Smallest value for an action card to be worth acquiring, after paying for it.
+    pub min_engine_value: f64,
+    /// Number of action cards owned, in hand and discarded, after which no more are acquired.
+    pub max_cards: usize,
+}
+
+impl Default for GreedyWeights {
+    fn default() -> Self {
+        Self {
+            coin: 1.0,
+            upgrade_step: 1.0,
+            missing_cube: 1.5,
+            overflow_cube: 2.0,
+            min_progress: 0.5,
+            engine: 1.0,
+            min_engine_value: 2.0,
+            max_cards: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// A bot following simple rules, in order:
+///
+/// 1. Claim the affordable points card with the most points per cube.
+/// 2. Play the card that moves the caravan closest to the cost of a target points card.
+/// 3. Acquire the action card that best improves the hand.
+/// 4. Rest.
+///
+/// An overflowing caravan discards the cubes least useful for the target card.
+pub struct GreedyBot {
+    weights: GreedyWeights,
+}
+
+impl GreedyBot {
+    /// Creates a bot with the given weights.
+    pub fn new(weights: GreedyWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Get the weights of this bot.
+    pub fn get_weights(&self) -> &GreedyWeights {
+        &self.weights
+    }
+
+    /// Get the distance from a caravan to a points card's cost, in weighted upgrade steps and missing cubes.
+    ///
+    /// The most valuable cubes of the cost are covered first, each by the most valuable cube in the caravan that is
+    /// not more valuable than it, since cubes can be upgraded but never downgraded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, bots::greedy::GreedyBot};
+    /// let bot = GreedyBot::default();
+    /// assert_eq!(bot.distance(&spice_amount!(2, 0, 0, 0), &spice_amount!(2, 0, 0, 0)), 0.0);
+    /// // The turmeric is upgraded twice to cover the cardamon, and the saffron is missing: 1 cube and 3 steps.
+    /// assert_eq!(bot.distance(&spice_amount!(1, 0, 0, 0), &spice_amount!(0, 1, 1, 0)), 4.5);
+    /// ```
+    pub fn distance(&self, caravan: &SpiceAmount, cost: &SpiceAmount) -> f64 {
+        let mut available = caravan.vector;
+        let mut distance = 0.0;
+        for (level, needed) in cost.vector.iter().enumerate().rev() {
+            for _ in 0..*needed {
+                match (0..=level).rev().find(|lower| available[*lower] > 0) {
+                    Some(lower) => {
+                        available[lower] -= 1;
+                        distance += (level - lower) as f64 * self.weights.upgrade_step;
+                    }
+                    None => {
+                        distance +=
+                            self.weights.missing_cube + level as f64 * self.weights.upgrade_step;
+                    }
+                }
+            }
+        }
+        distance
+    }
+
+    /// Get the distance from a caravan to a points card, counting cubes that would have to be discarded.
+    fn caravan_distance(&self, caravan: &SpiceAmount, target: &PointsCard) -> f64 {
+        let excess = caravan.total().saturating_sub(MAX_CARAVAN_SIZE);
+        self.distance(caravan, &target.cost) + excess as f64 * self.weights.overflow_cube
+    }
+
+    /// Get the value of a points card in the row, with the coin above it.
+    fn points_value(&self, view: &GameView, slot: usize) -> f64 {
+        let coin = view
+            .coins
+            .coin_for_slot(slot)
+            .map_or(0, |coin| coin.points());
+        view.points_row[slot].points as f64 + coin as f64 * self.weights.coin
+    }
+
+    /// Get the points card the player works towards: the best value for the distance left to afford it.
+    fn target(&self, view: &GameView) -> Option<PointsCard> {
+        let caravan = view.player().get_caravan().get_spice_amount();
+        (0..view.points_row.len())
+            .map(|slot| {
+                let card = view.points_row[slot];
+                (
+                    card,
+                    self.points_value(view, slot) / (1.0 + self.distance(&caravan, &card.cost)),
+                )
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(card, _)| card)
+    }
+
+    /// Get the caravan the player would have after an action, or `None` if it cannot be played.
+    fn caravan_after(view: &GameView, action: &PlayerAction) -> Option<SpiceAmount> {
+        let mut player = view.player().clone();
+        action.play(&mut player).ok()?;
+        Some(player.get_caravan().get_spice_amount())
+    }
+
+    /// Get the value of an action card for the player's engine, in spice levels produced per play.
+    fn engine_value(&self, card: &ActionCard) -> f64 {
+        let levels =
+            |spices: &SpiceAmount| spices.cubes().map(|cube| cube as u8 as f64).sum::<f64>();
+        let value = match card {
+            ActionCard::Gain(output) => levels(output),
+            ActionCard::Exchange(input, output) => levels(output) - levels(input),
+            ActionCard::Upgrade(steps) => *steps as f64,
+        };
+        value * self.weights.engine
+    }
+}
+
+impl Bot for GreedyBot {
+    fn name(&self) -> String {
+        "greedy".to_string()
+    }
+
+    /// Chooses an action following the rules of [`GreedyBot`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NoLegalActions` if there is nothing to play.
+    fn choose_action(&mut self, view: &GameView) -> Result<PlayerAction, GameErrors> {
+        let actions = &view.legal_actions;
+        let first = actions.first().ok_or(GameErrors::NoLegalActions)?;
+        let target = self.target(view);
+        let by_distance = |action: &&PlayerAction| {
+            let caravan = Self::caravan_after(view, action).unwrap_or_default();
+            target.map_or(0.0, |target| self.caravan_distance(&caravan, &target))
+        };
+
+        if matches!(first, PlayerAction::Discard(_)) {
+            return Ok(actions
+                .iter()
+                .min_by(|a, b| by_distance(a).total_cmp(&by_distance(b)))
+                .unwrap_or(first)
+                .clone());
+        }
+
+        let best_score = actions
+            .iter()
+            .filter_map(|action| match action {
+                PlayerAction::Score(card) => {
+                    let slot = view.points_row.iter().position(|x| x == card)?;
+                    let value = self.points_value(view, slot) / card.cost.total().max(1) as f64;
+                    Some((action, value))
+                }
+                _ => None,
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((action, _)) = best_score {
+            return Ok(action.clone());
+        }
+
+        let current = target.map_or(0.0, |target| {
+            self.caravan_distance(&view.player().get_caravan().get_spice_amount(), &target)
+        });
+        let best_play = actions
+            .iter()
+            .filter(|action| matches!(action, PlayerAction::PlayCard(..)))
+            .map(|action| (action, by_distance(&action)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((action, distance)) = best_play {
+            if current - distance >= self.weights.min_progress {
+                return Ok(action.clone());
+            }
+        }
+
+        let player = view.player();
+        if player.get_hand().len() + player.get_discard_pile().len() < self.weights.max_cards {
+            let best_acquisition = actions
+                .iter()
+                .filter_map(|action| match action {
+                    PlayerAction::AcquireCard(card, payment) => {
+                        let slot = view.merchant_row.iter().find(|slot| slot.card == *card)?;
+                        let value = self.engine_value(card)
+                            + self.engine_value(&ActionCard::Gain(slot.spices))
+                            - self.engine_value(&ActionCard::Gain(*payment));
+                        Some((action, value))
+                    }
+                    _ => None,
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+            if let Some((action, value)) = best_acquisition {
+                if value >= self.weights.min_engine_value {
+                    return Ok(action.clone());
+                }
+            }
+        }
+
+        if let Some(rest) = actions.iter().find(|action| **action == PlayerAction::Rest) {
+            return Ok(rest.clone());
+        }
+        Ok(best_play.map_or(first, |(action, _)| action).clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bots::greedy::{GreedyBot, GreedyWeights};
+    use crate::bots::{play_out, random::RandomBot, Bot, DEFAULT_MAX_ROUNDS};
+    use crate::game::Game;
+    use crate::player::PlayerAction;
+
+    #[test]
+    fn test_claims_affordable_card() {
+        let mut game = Game::new(3, 12).unwrap();
+        let mut bot = GreedyBot::default();
+        for _ in 0..60 {
+            let view = game.view();
+            let action = bot.choose_action(&view).unwrap();
+            let affordable = view
+                .legal_actions
+                .iter()
+                .any(|x| matches!(x, PlayerAction::Score(_)));
+            assert_eq!(affordable, matches!(action, PlayerAction::Score(_)));
+            game.play(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_beats_random_bot() {
+        let mut wins = 0;
+        for seed in 0..20 {
+            let mut game = Game::new(2, seed).unwrap();
+            let greedy_seat = seed as usize % 2;
+            let mut bots: Vec<Box<dyn Bot>> = vec![
+                Box::new(RandomBot::new(seed)),
+                Box::new(RandomBot::new(seed)),
+            ];
+            bots[greedy_seat] = Box::new(GreedyBot::new(GreedyWeights::default()));
+            play_out(&mut game, &mut bots, DEFAULT_MAX_ROUNDS).unwrap();
+            if game.standings()[0].player == greedy_seat {
+                wins += 1;
+            }
+        }
+        assert!(wins >= 18, "greedy bot won {wins} out of 20 games");
+    }
+}
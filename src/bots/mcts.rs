@@ -0,0 +1,332 @@
+use super::greedy::{GreedyBot, GreedyWeights};
+use super::random::RandomBot;
+use super::Bot;
+use crate::errors::GameErrors;
+use crate::game::{view::GameView, Game};
+use crate::player::PlayerAction;
+use crate::rng::Rng;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How long an [`MctsBot`] searches before choosing an action.
+pub enum MctsBudget {
+    /// A fixed number of iterations, which keeps the bot reproducible.
+    Iterations(u32),
+    /// A wall-clock time limit.
+    Time(Duration),
+}
+
+/// Score lead, in points, for which a player's reward is about 0.88.
+///
+/// It is large enough for the rewards to still tell actions apart in lopsided games.
+const REWARD_SCALE: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How an [`MctsBot`] plays the rollouts evaluating new tree nodes.
+pub enum MctsRollout {
+    /// Uniformly random legal actions, see [`RandomBot`].
+    Random,
+    /// The actions of a [`GreedyBot`] with the given weights, slower but more realistic.
+    Greedy(GreedyWeights),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The settings of an [`MctsBot`].
+pub struct MctsConfig {
+    /// How long to search for each action.
+    pub budget: MctsBudget,
+    /// The UCT exploration constant; higher values try less promising actions more often.
+    pub exploration: f64,
+    /// How the rollouts are played.
+    pub rollout: MctsRollout,
+    /// Number of actions played after leaving the tree before scoring the game as it stands.
+    pub rollout_limit: usize,
+    /// Seed for the determinizations and the random rollouts.
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            budget: MctsBudget::Iterations(500),
+            exploration: 0.1,
+            rollout: MctsRollout::Greedy(GreedyWeights::default()),
+            rollout_limit: 300,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    /// The action leading to this node, `None` for the root.
+    action: Option<PlayerAction>,
+    /// Index of the player who played `action`.
+    player: usize,
+    children: Vec<usize>,
+    visits: u32,
+    /// Number of times this node's action was legal when its parent was visited.
+    availability: u32,
+    /// Sum of the rewards of `player` over the visits.
+    reward: f64,
+}
+
+#[derive(Debug, Clone)]
+/// A bot using Monte Carlo Tree Search with UCT.
+///
+/// The order of the decks is hidden, so each iteration searches a different determinization of the game (see
+/// [`GameView::determinize`]), sharing a single tree whose actions are only considered when they are legal in the
+/// sampled game (information set MCTS). Leaves are evaluated with rollouts, rewarding each player by their
+/// score lead over the other players.
+pub struct MctsBot {
+    config: MctsConfig,
+    rng: Rng,
+    root_visits: Vec<(PlayerAction, u32)>,
+}
+
+impl MctsBot {
+    /// Creates a bot with the given settings.
+    pub fn new(config: MctsConfig) -> Self {
+        Self {
+            config,
+            rng: Rng::new(config.seed),
+            root_visits: Vec::new(),
+        }
+    }
+
+    /// Get the settings of this bot.
+    pub fn get_config(&self) -> &MctsConfig {
+        &self.config
+    }
+
+    /// Get the visit count of each legal action in the last search, most visited first.
+    ///
+    /// The visit counts tell how promising the search found each action, for hints and analysis.
+    pub fn get_root_visits(&self) -> &[(PlayerAction, u32)] {
+        &self.root_visits
+    }
+
+    /// Searches the game from the given view and returns the visit count of each legal action, most visited first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{bots::mcts::{MctsBot, MctsBudget, MctsConfig}, game::Game};
+    /// let config = MctsConfig { budget: MctsBudget::Iterations(50), ..MctsConfig::default() };
+    /// let visits = MctsBot::new(config).search(&Game::new(2, 3).unwrap().view()).unwrap();
+    /// assert_eq!(visits.iter().map(|(_, visits)| visits).sum::<u32>(), 50);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NoLegalActions` if there is nothing to play.
+    pub fn search(&mut self, view: &GameView) -> Result<Vec<(PlayerAction, u32)>, GameErrors> {
+        if view.legal_actions.is_empty() {
+            return Err(GameErrors::NoLegalActions);
+        }
+
+        let mut nodes = vec![Node {
+            action: None,
+            player: view.seat,
+            children: Vec::new(),
+            visits: 0,
+            availability: 0,
+            reward: 0.0,
+        }];
+        let start = Instant::now();
+        let mut iterations = 0;
+        while match self.config.budget {
+            MctsBudget::Iterations(limit) => iterations < limit,
+            MctsBudget::Time(limit) => iterations == 0 || start.elapsed() < limit,
+        } {
+            let mut game = view.determinize(&mut self.rng);
+            self.iterate(&mut nodes, &mut game)?;
+            iterations += 1;
+        }
+
+        let mut visits: Vec<_> = nodes[0]
+            .children
+            .iter()
+            .filter_map(|child| Some((nodes[*child].action.clone()?, nodes[*child].visits)))
+            .collect();
+        for action in &view.legal_actions {
+            if !visits.iter().any(|(x, _)| x == action) {
+                visits.push((action.clone(), 0));
+            }
+        }
+        visits.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Ok(visits)
+    }
+
+    /// Runs one iteration: selects a path down the tree, expands one action, plays a rollout and backs up the rewards.
+    fn iterate(&mut self, nodes: &mut Vec<Node>, game: &mut Game) -> Result<(), GameErrors> {
+        let mut node = 0;
+        let mut path = vec![0];
+
+        while !game.is_over() {
+            let legal = game.legal_actions();
+            let available: Vec<_> = nodes[node]
+                .children
+                .iter()
+                .copied()
+                .filter(|child| {
+                    legal
+                        .iter()
+                        .any(|action| nodes[*child].action.as_ref() == Some(action))
+                })
+                .collect();
+            for child in &available {
+                nodes[*child].availability += 1;
+            }
+
+            let untried: Vec<_> = legal
+                .iter()
+                .filter(|action| {
+                    !available
+                        .iter()
+                        .any(|child| nodes[*child].action.as_ref() == Some(action))
+                })
+                .collect();
+            let player = game.get_current_player_index();
+            if !untried.is_empty() {
+                let action = untried[self.rng.below(untried.len())].clone();
+                game.play(action.clone())?;
+                nodes.push(Node {
+                    action: Some(action),
+                    player,
+                    children: Vec::new(),
+                    visits: 0,
+                    availability: 1,
+                    reward: 0.0,
+                });
+                let child = nodes.len() - 1;
+                nodes[node].children.push(child);
+                path.push(child);
+                break;
+            }
+
+            let exploration = self.config.exploration;
+            let ucb = |child: &usize| {
+                let child = &nodes[*child];
+                let visits = child.visits.max(1) as f64;
+                child.reward / visits
+                    + exploration * ((child.availability as f64).ln() / visits).sqrt()
+            };
+            let best = available
+                .iter()
+                .copied()
+                .max_by(|a, b| ucb(a).total_cmp(&ucb(b)))
+                .ok_or(GameErrors::InternalLogicError)?;
+            game.play(
+                nodes[best]
+                    .action
+                    .clone()
+                    .ok_or(GameErrors::InternalLogicError)?,
+            )?;
+            node = best;
+            path.push(best);
+        }
+
+        let mut rollout_bot: Box<dyn Bot> = match self.config.rollout {
+            MctsRollout::Random => Box::new(RandomBot::new(self.rng.next_u64())),
+            MctsRollout::Greedy(weights) => Box::new(GreedyBot::new(weights)),
+        };
+        for _ in 0..self.config.rollout_limit {
+            if game.is_over() {
+                break;
+            }
+            game.play(rollout_bot.choose_action(&game.view())?)?;
+        }
+
+        let rewards = rewards(game);
+        for node in path {
+            nodes[node].visits += 1;
+            nodes[node].reward += rewards[nodes[node].player];
+        }
+        Ok(())
+    }
+}
+
+/// Get each player's reward for the game as it stands, between 0 and 1, from their lead over the best other player.
+///
+/// Rewarding the margin rather than only the win still tells good actions from bad ones in lost or won positions.
+fn rewards(game: &Game) -> Vec<f64> {
+    let scores: Vec<_> = game
+        .get_players()
+        .iter()
+        .map(|player| player.score() as f64)
+        .collect();
+    (0..scores.len())
+        .map(|player| {
+            let best_other = (0..scores.len())
+                .filter(|other| *other != player)
+                .map(|other| scores[other])
+                .fold(0.0, f64::max);
+            0.5 + 0.5 * ((scores[player] - best_other) / REWARD_SCALE).tanh()
+        })
+        .collect()
+}
+
+impl Bot for MctsBot {
+    fn name(&self) -> String {
+        "mcts".to_string()
+    }
+
+    /// Chooses the most visited action of a search, see [`MctsBot::search`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NoLegalActions` if there is nothing to play.
+    fn choose_action(&mut self, view: &GameView) -> Result<PlayerAction, GameErrors> {
+        self.root_visits = self.search(view)?;
+        Ok(self.root_visits[0].0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bots::mcts::{MctsBot, MctsBudget, MctsConfig};
+    use crate::bots::{play_out, random::RandomBot, Bot};
+    use crate::game::Game;
+
+    fn config(iterations: u32, seed: u64) -> MctsConfig {
+        MctsConfig {
+            budget: MctsBudget::Iterations(iterations),
+            seed,
+            ..MctsConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_search_is_reproducible() {
+        let view = Game::new(3, 21).unwrap().view();
+        let visits = MctsBot::new(config(30, 1)).search(&view).unwrap();
+
+        assert_eq!(visits, MctsBot::new(config(30, 1)).search(&view).unwrap());
+        assert_eq!(visits.len(), view.legal_actions.len());
+        assert!(visits.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn test_beats_random_bot() {
+        let mut wins = 0;
+        for seed in 0..20 {
+            let mut game = Game::new(2, seed).unwrap();
+            let mcts_seat = seed as usize % 2;
+            let mut bots: Vec<Box<dyn Bot>> = vec![
+                Box::new(RandomBot::new(seed)),
+                Box::new(RandomBot::new(seed)),
+            ];
+            // Short rollouts keep the 20 games fast enough for a debug build.
+            bots[mcts_seat] = Box::new(MctsBot::new(MctsConfig {
+                rollout_limit: 2,
+                ..config(20, seed)
+            }));
+            play_out(&mut game, &mut bots, 1000).unwrap();
+            if game.standings()[0].player == mcts_seat {
+                wins += 1;
+            }
+        }
+        assert!(wins >= 18, "tree search bot won {wins} out of 20 games");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::errors::GameErrors;
+use crate::game::{view::GameView, Game};
+use crate::player::PlayerAction;
+
+pub mod external;
+pub mod greedy;
+pub mod mcts;
+pub mod random;
+
+/// A player that chooses its own actions, such as a computer opponent.
+pub trait Bot {
+    /// Get a short name for the bot, shown in logs and results.
+    fn name(&self) -> String;
+
+    /// Chooses an action for the player to move, which should be one of `view.legal_actions`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bot cannot choose an action, such as `GameErrors::NoLegalActions`.
+    fn choose_action(&mut self, view: &GameView) -> Result<PlayerAction, GameErrors>;
+}
+
+/// Rounds after which games between bots are given up, in case the bots never end them.
+pub const DEFAULT_MAX_ROUNDS: usize = 100;
+
+/// Lets the bots play a game until it is over, each bot playing the seat of the same index, for at most `max_rounds`
+/// more rounds. Discards do not count as turns.
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{bots::{greedy::GreedyBot, play_out, random::RandomBot, Bot, DEFAULT_MAX_ROUNDS}, game::Game};
+/// let mut game = Game::new(2, 5).unwrap();
+/// let mut bots: Vec<Box<dyn Bot>> = vec![Box::new(GreedyBot::default()), Box::new(RandomBot::new(2))];
+/// play_out(&mut game, &mut bots, DEFAULT_MAX_ROUNDS).unwrap();
+/// assert!(game.is_over());
+/// ```
+///
+/// # Errors
+///
+/// * `GameErrors::BotCountMismatch` if there is not one bot per player.
+/// * `GameErrors::RoundLimitReached` if the game is not over after `max_rounds` rounds.
+/// * Any error returned by a bot, or by [`Game::play`] for an illegal action.
+///
+/// ```
+/// use libcsr::{bots::{play_out, random::RandomBot, Bot}, errors::GameErrors, game::Game};
+/// let mut game = Game::new(2, 5).unwrap();
+/// let mut bots: Vec<Box<dyn Bot>> = vec![Box::new(RandomBot::new(1)), Box::new(RandomBot::new(2))];
+/// assert_eq!(play_out(&mut game, &mut bots, 3), Err(GameErrors::RoundLimitReached(3)));
+/// ```
+pub fn play_out(
+    game: &mut Game,
+    bots: &mut [Box<dyn Bot>],
+    max_rounds: usize,
+) -> Result<(), GameErrors> {
+    let players = game.get_players().len();
+    if bots.len() != players {
+        return Err(GameErrors::BotCountMismatch(players, bots.len()));
+    }
+
+    let mut turns = 0;
+    while !game.is_over() {
+        if turns == max_rounds * players
+            && !game.get_current_player().get_caravan().is_overflowing()
+        {
+            return Err(GameErrors::RoundLimitReached(max_rounds));
+        }
+        let action = bots[game.get_current_player_index()].choose_action(&game.view())?;
+        if !matches!(action, PlayerAction::Discard(_)) {
+            turns += 1;
+        }
+        game.play(action)?;
+    }
+    Ok(())
+}
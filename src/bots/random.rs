@@ -0,0 +1,80 @@
+use super::Bot;
+use crate::errors::GameErrors;
+use crate::game::view::GameView;
+use crate::player::PlayerAction;
+use crate::rng::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A bot playing uniformly at random among the legal actions, as a baseline for stronger bots.
+pub struct RandomBot {
+    rng: Rng,
+}
+
+impl RandomBot {
+    /// Creates a bot whose choices are drawn from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Bot for RandomBot {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    /// Chooses one of the legal actions, each with the same probability.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NoLegalActions` if there is nothing to play.
+    fn choose_action(&mut self, view: &GameView) -> Result<PlayerAction, GameErrors> {
+        if view.legal_actions.is_empty() {
+            return Err(GameErrors::NoLegalActions);
+        }
+        Ok(view.legal_actions[self.rng.below(view.legal_actions.len())].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bots::{play_out, random::RandomBot, Bot, DEFAULT_MAX_ROUNDS};
+    use crate::errors::GameErrors;
+    use crate::game::Game;
+
+    #[test]
+    fn test_random_games_are_reproducible() {
+        let play = |seed| {
+            let mut game = Game::new(4, seed).unwrap();
+            let mut bots: Vec<Box<dyn Bot>> = (0..4)
+                .map(|seat| Box::new(RandomBot::new(seed + seat)) as Box<dyn Bot>)
+                .collect();
+            play_out(&mut game, &mut bots, DEFAULT_MAX_ROUNDS).unwrap();
+            game
+        };
+
+        let game = play(8);
+        assert!(game.is_over());
+        assert_eq!(game, play(8));
+        assert_ne!(game, play(9));
+    }
+
+    #[test]
+    fn test_no_legal_actions() {
+        let mut game = Game::new(2, 1).unwrap();
+        let mut bots: Vec<Box<dyn Bot>> = vec![Box::new(RandomBot::new(1))];
+        assert_eq!(
+            play_out(&mut game, &mut bots, DEFAULT_MAX_ROUNDS),
+            Err(GameErrors::BotCountMismatch(2, 1))
+        );
+
+        let mut bots: Vec<Box<dyn Bot>> =
+            vec![Box::new(RandomBot::new(1)), Box::new(RandomBot::new(2))];
+        play_out(&mut game, &mut bots, DEFAULT_MAX_ROUNDS).unwrap();
+        assert_eq!(
+            bots[0].choose_action(&game.view()),
+            Err(GameErrors::NoLegalActions)
+        );
+    }
+}
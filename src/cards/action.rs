@@ -7,7 +7,7 @@ pub const STARTING_ACTION_CARDS: [ActionCard; 2] = [
     ActionCard::Upgrade(2),
 ];
 
-pub const PURCHASABLE_ACTION_CARDS: [ActionCard; 9] = [
+pub const PURCHASABLE_ACTION_CARDS: [ActionCard; 43] = [
     // Spice cards.
     ActionCard::Gain(spice_amount!(3, 0, 0, 0)),
     ActionCard::Gain(spice_amount!(4, 0, 0, 0)),
@@ -20,4 +20,38 @@ pub const PURCHASABLE_ACTION_CARDS: [ActionCard; 9] = [
     // Single upgrade card in the deck.
     ActionCard::Upgrade(3),
     // Exchange cards.
+    ActionCard::Exchange(spice_amount!(2, 0, 0, 0), spice_amount!(0, 0, 1, 0)),
+    ActionCard::Exchange(spice_amount!(3, 0, 0, 0), spice_amount!(0, 0, 0, 1)),
+    ActionCard::Exchange(spice_amount!(3, 0, 0, 0), spice_amount!(0, 3, 0, 0)),
+    ActionCard::Exchange(spice_amount!(3, 0, 0, 0), spice_amount!(0, 1, 1, 0)),
+    ActionCard::Exchange(spice_amount!(4, 0, 0, 0), spice_amount!(0, 0, 2, 0)),
+    ActionCard::Exchange(spice_amount!(4, 0, 0, 0), spice_amount!(0, 2, 1, 0)),
+    ActionCard::Exchange(spice_amount!(5, 0, 0, 0), spice_amount!(0, 0, 0, 2)),
+    ActionCard::Exchange(spice_amount!(5, 0, 0, 0), spice_amount!(0, 0, 3, 0)),
+    ActionCard::Exchange(spice_amount!(0, 2, 0, 0), spice_amount!(0, 0, 2, 0)),
+    ActionCard::Exchange(spice_amount!(0, 2, 0, 0), spice_amount!(1, 0, 0, 1)),
+    ActionCard::Exchange(spice_amount!(0, 2, 0, 0), spice_amount!(3, 0, 1, 0)),
+    ActionCard::Exchange(spice_amount!(0, 3, 0, 0), spice_amount!(0, 0, 3, 0)),
+    ActionCard::Exchange(spice_amount!(0, 3, 0, 0), spice_amount!(0, 0, 0, 2)),
+    ActionCard::Exchange(spice_amount!(0, 3, 0, 0), spice_amount!(1, 0, 1, 1)),
+    ActionCard::Exchange(spice_amount!(0, 5, 0, 0), spice_amount!(0, 0, 0, 3)),
+    ActionCard::Exchange(spice_amount!(0, 0, 1, 0), spice_amount!(0, 3, 0, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 1, 0), spice_amount!(1, 2, 0, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 1, 0), spice_amount!(4, 0, 0, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 2, 0), spice_amount!(0, 0, 0, 2)),
+    ActionCard::Exchange(spice_amount!(0, 0, 2, 0), spice_amount!(0, 2, 0, 1)),
+    ActionCard::Exchange(spice_amount!(0, 0, 2, 0), spice_amount!(3, 0, 0, 1)),
+    ActionCard::Exchange(spice_amount!(0, 0, 3, 0), spice_amount!(0, 0, 0, 3)),
+    ActionCard::Exchange(spice_amount!(0, 0, 0, 1), spice_amount!(0, 0, 2, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 0, 1), spice_amount!(0, 3, 0, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 0, 1), spice_amount!(1, 1, 1, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 0, 1), spice_amount!(2, 0, 1, 0)),
+    ActionCard::Exchange(spice_amount!(0, 0, 0, 2), spice_amount!(0, 1, 3, 0)),
+    ActionCard::Exchange(spice_amount!(1, 1, 0, 0), spice_amount!(0, 0, 0, 1)),
+    ActionCard::Exchange(spice_amount!(1, 0, 1, 0), spice_amount!(0, 1, 0, 1)),
+    ActionCard::Exchange(spice_amount!(2, 1, 0, 0), spice_amount!(0, 0, 2, 0)),
+    ActionCard::Exchange(spice_amount!(2, 0, 1, 0), spice_amount!(0, 0, 0, 2)),
+    ActionCard::Exchange(spice_amount!(0, 1, 0, 1), spice_amount!(0, 0, 3, 0)),
+    ActionCard::Exchange(spice_amount!(1, 1, 1, 0), spice_amount!(0, 0, 0, 2)),
+    ActionCard::Exchange(spice_amount!(2, 2, 0, 0), spice_amount!(0, 0, 0, 2)),
 ];
@@ -1,17 +1,108 @@
 use crate::errors::GameErrors;
-use crate::spice::SpiceAmount;
+use crate::spice::{SpiceAmount, UpgradePlan};
+use std::fmt;
+use std::str::FromStr;
 
 pub mod action;
 pub mod points;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum ActionCard {
     Gain(SpiceAmount),
-    Exchange(fn(SpiceAmount, u8) -> Result<SpiceAmount, GameErrors>),
+    /// Trade the first `SpiceAmount` for the second, as many times as the caravan allows.
+    Exchange(SpiceAmount, SpiceAmount),
     Upgrade(u8),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+/// The choices a player makes when playing an [`ActionCard`] from their hand.
+///
+/// Each variant corresponds to the `ActionCard` variant of the same name.
+pub enum CardChoice {
+    /// `Gain` cards require no choice.
+    Gain,
+    /// The number of times an `Exchange` card is traded.
+    Exchange(u8),
+    /// The cubes an `Upgrade` card is applied to, each with its number of steps.
+    Upgrade(UpgradePlan),
+}
+
+impl ActionCard {
+    /// Apply the effect of this card to a `SpiceAmount`, returning the resulting `SpiceAmount`.
+    ///
+    /// # Examples
+    ///
+    /// Trade 2 turmeric for 1 cardamon twice:
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{ActionCard, CardChoice}};
+    /// let card = ActionCard::Exchange(spice_amount!(2, 0, 0, 0), spice_amount!(0, 0, 1, 0));
+    /// let result = card.play(&spice_amount!(5, 0, 0, 0), &CardChoice::Exchange(2));
+    /// assert_eq!(result, Ok(spice_amount!(1, 0, 2, 0)));
+    /// ```
+    ///
+    /// Upgrade one turmeric cube by 1 step and another by 1 step:
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{ActionCard, CardChoice}, spice::SpiceCube::Turmeric};
+    /// let card = ActionCard::Upgrade(2);
+    /// let choice = CardChoice::Upgrade(vec![(Turmeric, 1), (Turmeric, 1)]);
+    /// let result = card.play(&spice_amount!(3, 0, 0, 0), &choice);
+    /// assert_eq!(result, Ok(spice_amount!(1, 2, 0, 0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidCardChoice` if the choice does not match the card, an exchange is traded zero times or more
+    /// times than any caravan could pay for, or an upgrade has no targets.
+    ///
+    /// Returns any of the errors of [`SpiceAmount::apply_upgrades`] for `Upgrade` cards.
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{ActionCard, CardChoice}, errors::GameErrors, spice::SpiceCube::Turmeric};
+    /// let card = ActionCard::Upgrade(2);
+    /// let choice = CardChoice::Upgrade(vec![(Turmeric, 3)]);
+    /// let result = card.play(&spice_amount!(3, 0, 0, 0), &choice);
+    /// assert_eq!(result, Err(GameErrors::UpgradeBudgetExceeded(2)));
+    /// ```
+    pub fn play(
+        &self,
+        spice_amount: &SpiceAmount,
+        choice: &CardChoice,
+    ) -> Result<SpiceAmount, GameErrors> {
+        match (self, choice) {
+            (ActionCard::Gain(gain), CardChoice::Gain) => Ok(spice_amount.add(gain)),
+            (ActionCard::Exchange(input, output), CardChoice::Exchange(times)) if *times > 0 => {
+                let cost = input
+                    .multiply(*times)
+                    .map_err(|_| GameErrors::InvalidCardChoice)?;
+                let gain = output
+                    .multiply(*times)
+                    .map_err(|_| GameErrors::InvalidCardChoice)?;
+                Ok(spice_amount.subtract(&cost)?.add(&gain))
+            }
+            (ActionCard::Upgrade(budget), CardChoice::Upgrade(upgrades))
+                if !upgrades.is_empty() =>
+            {
+                spice_amount.apply_upgrades(upgrades, *budget)
+            }
+            _ => Err(GameErrors::InvalidCardChoice),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointsCard {
     pub points: u8,
     pub cost: SpiceAmount,
@@ -24,9 +115,228 @@ impl PointsCard {
     }
 }
 
+/// Writes an action card as the spices it gives (`YY`), as an exchange of spices (`YYY>GG`), or as an upgrade with its
+/// number of steps (`U2`). Spices are written as described for [`SpiceAmount`].
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{spice_amount, cards::ActionCard};
+/// let card = ActionCard::Exchange(spice_amount!(3, 0, 0, 0), spice_amount!(0, 0, 2, 0));
+/// assert_eq!(card.to_string(), "YYY>GG");
+/// assert_eq!("YYY>GG".parse(), Ok(card));
+/// assert_eq!("U2".parse(), Ok(ActionCard::Upgrade(2)));
+/// assert_eq!("YR".parse(), Ok(ActionCard::Gain(spice_amount!(1, 1, 0, 0))));
+/// ```
+impl fmt::Display for ActionCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionCard::Gain(output) => write!(f, "{output}"),
+            ActionCard::Exchange(input, output) => write!(f, "{input}>{output}"),
+            ActionCard::Upgrade(steps) => write!(f, "U{steps}"),
+        }
+    }
+}
+
+/// Parses the notation written by `Display`.
+///
+/// # Errors
+///
+/// Returns `GameErrors::InvalidNotation` with the column at which the string stops being a valid card.
+///
+/// ```
+/// use libcsr::{cards::ActionCard, errors::GameErrors};
+/// let result = "YY>GX".parse::<ActionCard>();
+/// assert_eq!(result, Err(GameErrors::InvalidNotation("YY>GX".to_string(), 5, "'X' is not a spice, expected one of Y, R, G or B".to_string())));
+/// ```
+impl FromStr for ActionCard {
+    type Err = GameErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(steps) = s.strip_prefix('U') {
+            return match steps.parse() {
+                Ok(0) | Err(_) => Err(GameErrors::InvalidNotation(
+                    s.to_string(),
+                    2,
+                    "expected the number of upgrade steps".to_string(),
+                )),
+                Ok(steps) => Ok(ActionCard::Upgrade(steps)),
+            };
+        }
+
+        match s.split_once('>') {
+            Some((input, output)) => Ok(ActionCard::Exchange(
+                parse_part(s, input, 0)?,
+                parse_part(s, output, input.chars().count() + 1)?,
+            )),
+            None => Ok(ActionCard::Gain(s.parse()?)),
+        }
+    }
+}
+
+/// Writes a points card as its points and its cost, separated by a colon (`12:RRGG`).
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{points_card, cards::PointsCard};
+/// let card = points_card!(12, [0, 2, 2, 0]);
+/// assert_eq!(card.to_string(), "12:RRGG");
+/// assert_eq!("12:RRGG".parse(), Ok(card));
+/// ```
+impl fmt::Display for PointsCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.points, self.cost)
+    }
+}
+
+/// Parses the notation written by `Display`.
+///
+/// # Errors
+///
+/// Returns `GameErrors::InvalidNotation` with the column at which the string stops being a valid card.
+///
+/// ```
+/// use libcsr::{cards::PointsCard, errors::GameErrors};
+/// let result = "12".parse::<PointsCard>();
+/// assert_eq!(result, Err(GameErrors::InvalidNotation("12".to_string(), 3, "expected ':' followed by the cost".to_string())));
+/// ```
+impl FromStr for PointsCard {
+    type Err = GameErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((points, cost)) = s.split_once(':') else {
+            return Err(GameErrors::InvalidNotation(
+                s.to_string(),
+                s.chars().count() + 1,
+                "expected ':' followed by the cost".to_string(),
+            ));
+        };
+        Ok(PointsCard {
+            points: points.parse().map_err(|_| {
+                GameErrors::InvalidNotation(
+                    s.to_string(),
+                    1,
+                    "expected the card's points".to_string(),
+                )
+            })?,
+            cost: parse_part(s, cost, points.chars().count() + 1)?,
+        })
+    }
+}
+
+/// Parses `part`, found `offset` characters into `s`, reporting notation errors against the whole of `s`.
+fn parse_part<T: FromStr<Err = GameErrors>>(
+    s: &str,
+    part: &str,
+    offset: usize,
+) -> Result<T, GameErrors> {
+    part.parse().map_err(|error| match error {
+        GameErrors::InvalidNotation(_, column, reason) => {
+            GameErrors::InvalidNotation(s.to_string(), offset + column, reason)
+        }
+        error => error,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{cards::PointsCard, errors::GameErrors, spice_amount};
+    use crate::{
+        cards::{
+            action::{PURCHASABLE_ACTION_CARDS, STARTING_ACTION_CARDS},
+            points::POINTS_CARDS,
+            ActionCard, CardChoice, PointsCard,
+        },
+        errors::GameErrors,
+        spice_amount,
+    };
+
+    #[test]
+    fn test_purchasable_cards_are_unique() {
+        for (idx, card) in PURCHASABLE_ACTION_CARDS.iter().enumerate() {
+            assert!(
+                !PURCHASABLE_ACTION_CARDS[idx + 1..].contains(card),
+                "{card:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_notation_round_trip() {
+        for card in PURCHASABLE_ACTION_CARDS
+            .iter()
+            .chain(&STARTING_ACTION_CARDS)
+        {
+            assert_eq!(card.to_string().parse(), Ok(*card));
+        }
+        for card in POINTS_CARDS {
+            assert_eq!(card.to_string().parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn test_notation_errors() {
+        let invalid = |s: &str, column, reason: &str| {
+            GameErrors::InvalidNotation(s.to_string(), column, reason.to_string())
+        };
+        assert_eq!(
+            "U".parse::<ActionCard>(),
+            Err(invalid("U", 2, "expected the number of upgrade steps"))
+        );
+        assert_eq!(
+            ">GG".parse::<ActionCard>(),
+            Err(invalid(">GG", 1, "expected spices, or - for none"))
+        );
+        assert_eq!(
+            "YY>".parse::<ActionCard>(),
+            Err(invalid("YY>", 4, "expected spices, or - for none"))
+        );
+        assert_eq!(
+            "x:YY".parse::<PointsCard>(),
+            Err(invalid("x:YY", 1, "expected the card's points"))
+        );
+        assert_eq!(
+            "12:RRQ".parse::<PointsCard>(),
+            Err(invalid(
+                "12:RRQ",
+                6,
+                "'Q' is not a spice, expected one of Y, R, G or B"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_exchange_failure() {
+        let card = ActionCard::Exchange(spice_amount!(0, 2, 0, 0), spice_amount!(0, 0, 2, 0));
+        let spice_amount = spice_amount!(1, 3, 0, 0);
+
+        assert_eq!(
+            card.play(&spice_amount, &CardChoice::Exchange(2)),
+            Err(GameErrors::CannotSubtractSpiceAmount(
+                spice_amount,
+                spice_amount!(0, 1, 0, 0)
+            ))
+        );
+        assert_eq!(
+            card.play(&spice_amount, &CardChoice::Exchange(0)),
+            Err(GameErrors::InvalidCardChoice)
+        );
+    }
+
+    #[test]
+    fn test_exchange_overflowing_times() {
+        let card = ActionCard::Exchange(spice_amount!(2, 0, 0, 0), spice_amount!(0, 0, 1, 0));
+        let spice_amount = spice_amount!(3, 0, 0, 0);
+
+        assert_eq!(
+            card.play(&spice_amount, &CardChoice::Exchange(128)),
+            Err(GameErrors::InvalidCardChoice)
+        );
+        assert_eq!(
+            card.play(&spice_amount, &CardChoice::Exchange(u8::MAX)),
+            Err(GameErrors::InvalidCardChoice)
+        );
+    }
 
     #[test]
     fn test_purchase() {
@@ -1,8 +1,14 @@
+use crate::game::{MAX_PLAYERS, MIN_PLAYERS};
 use crate::player::MAX_CARAVAN_SIZE;
 use crate::spice::SpiceAmount;
 use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum GameErrors {
     #[error("Cannot upgrade a spice to itself")]
     CannotUpgradeToSelf,
@@ -16,6 +22,102 @@ pub enum GameErrors {
     #[error("Cannot subtract spice amount from another spice amount")]
     CannotSubtractSpiceAmount(SpiceAmount, SpiceAmount),
 
+    #[error("The choice made does not match the card played")]
+    InvalidCardChoice,
+
+    #[error("Cannot upgrade more than {0} step(s) with this card")]
+    UpgradeBudgetExceeded(u8),
+
+    #[error("Card is not in the player's hand")]
+    CardNotInHand,
+
+    #[error("Cannot rest with an empty discard pile")]
+    EmptyDiscardPile,
+
+    #[error("Must discard exactly {0} spice(s) from the caravan")]
+    InvalidDiscard(usize),
+
+    #[error("Must discard {0} spice(s) from the caravan before taking another action")]
+    DiscardPending(usize),
+
+    #[error("Cannot discard spices from a caravan that is not overflowing")]
+    NothingToDiscard,
+
+    #[error("Card is not face-up in the market")]
+    CardNotInMarket,
+
+    #[error("Acquiring this card requires placing exactly {0} spice(s) on the cards to its left")]
+    InvalidAcquisitionPayment(usize),
+
+    #[error("The game is over")]
+    GameOver,
+
+    #[error("A game needs between {MIN_PLAYERS} and {MAX_PLAYERS} players, got {0}")]
+    InvalidNumberOfPlayers(usize),
+
+    #[error("Not a valid save file: {0}")]
+    InvalidSaveFile(String),
+
+    #[error("Unsupported save format version {0}")]
+    UnsupportedSaveVersion(u32),
+
+    #[error("Cannot access save file: {0}")]
+    SaveFileIo(String),
+
+    #[error("Invalid notation {0:?} at column {1}: {2}")]
+    InvalidNotation(String, usize, String),
+
+    #[error("Only games set up from a seed can be recorded")]
+    UnseededGame,
+
+    #[error("Invalid game record at line {0}: {1}")]
+    InvalidRecord(usize, String),
+
+    #[error("Illegal move at turn {0} ({1}): {2}")]
+    IllegalMove(usize, String, Box<GameErrors>),
+
+    #[error("There are no legal actions to choose from")]
+    NoLegalActions,
+
+    #[error("Expected {0} bots, one per player, got {1}")]
+    BotCountMismatch(usize, usize),
+
+    #[error("The game did not end within {0} rounds")]
+    RoundLimitReached(usize),
+
+    #[error("The game is not over yet")]
+    GameNotOver,
+
+    #[error("Expected {0} player names, one per player, got {1}")]
+    PlayerNamesMismatch(usize, usize),
+
+    #[error("Player {0:?} appears more than once in the game")]
+    DuplicatePlayerName(String),
+
+    #[error("Not a valid ratings file: {0}")]
+    InvalidRatingsFile(String),
+
+    #[error("Cannot access ratings file: {0}")]
+    RatingsFileIo(String),
+
+    #[error("Bot {0:?} forfeits: it {1}")]
+    BotForfeit(String, String),
+
+    #[error("Network error: {0}")]
+    NetworkIo(String),
+
+    #[error("Invalid network message: {0}")]
+    InvalidMessage(String),
+
+    #[error("{0} left the game")]
+    PlayerLeft(String),
+
+    #[error("Spice amount is too large")]
+    SpiceAmountOverflow,
+
+    #[error("Cannot move more than {0} spice(s) with this action")]
+    TooManySpices(usize),
+
     #[error("Internal logic error occurred")]
     InternalLogicError,
 }
@@ -0,0 +1,817 @@
+use crate::cards::action::PURCHASABLE_ACTION_CARDS;
+use crate::cards::points::POINTS_CARDS;
+use crate::cards::{ActionCard, PointsCard};
+use crate::errors::GameErrors;
+use crate::player::{Caravan, Player, PlayerAction};
+use crate::rng::Rng;
+use crate::spice::SpiceAmount;
+use crate::spice_amount;
+
+pub mod moves;
+pub mod record;
+#[cfg(feature = "serde")]
+pub mod save;
+pub mod scoring;
+pub mod view;
+
+/// Minimum number of players in a game.
+pub const MIN_PLAYERS: usize = 2;
+
+/// Maximum number of players in a game.
+pub const MAX_PLAYERS: usize = 5;
+
+/// Number of face-up points cards in the points-card row.
+pub const POINTS_ROW_SIZE: usize = 5;
+
+/// Number of face-up action cards in the merchant row.
+pub const MERCHANT_ROW_SIZE: usize = 6;
+
+/// The spices each seat starts with, from the 1st to the 5th player.
+pub const STARTING_CARAVANS: [SpiceAmount; MAX_PLAYERS] = [
+    spice_amount!(3, 0, 0, 0),
+    spice_amount!(4, 0, 0, 0),
+    spice_amount!(4, 0, 0, 0),
+    spice_amount!(3, 1, 0, 0),
+    spice_amount!(3, 1, 0, 0),
+];
+
+/// Number of gold and of silver coins per player in the coin supply.
+pub const COINS_PER_PLAYER: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+/// A coin awarded for claiming one of the first two points cards in the row.
+pub enum Coin {
+    Gold,
+    Silver,
+}
+
+impl Coin {
+    /// Get the points the coin is worth at the end of the game.
+    pub fn points(&self) -> u8 {
+        match self {
+            Coin::Gold => 3,
+            Coin::Silver => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The coins left above the points-card row.
+///
+/// Gold coins sit above the first slot and silver coins above the second. Once the gold coins run out, the silver
+/// coins move to the first slot.
+pub struct CoinSupply {
+    pub gold: u8,
+    pub silver: u8,
+}
+
+impl CoinSupply {
+    /// Creates the supply for the given number of players.
+    pub fn new(num_players: usize) -> Self {
+        let coins = COINS_PER_PLAYER * num_players as u8;
+        Self {
+            gold: coins,
+            silver: coins,
+        }
+    }
+
+    /// Get the coin sitting above a slot of the points-card row, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::{Coin, CoinSupply};
+    /// let supply = CoinSupply { gold: 1, silver: 4 };
+    /// assert_eq!(supply.coin_for_slot(0), Some(Coin::Gold));
+    /// assert_eq!(supply.coin_for_slot(1), Some(Coin::Silver));
+    /// assert_eq!(supply.coin_for_slot(2), None);
+    ///
+    /// let supply = CoinSupply { gold: 0, silver: 4 };
+    /// assert_eq!(supply.coin_for_slot(0), Some(Coin::Silver));
+    /// assert_eq!(supply.coin_for_slot(1), None);
+    /// ```
+    pub fn coin_for_slot(&self, slot: usize) -> Option<Coin> {
+        let coins = [
+            (self.gold > 0).then_some(Coin::Gold),
+            (self.silver > 0).then_some(Coin::Silver),
+        ];
+        coins.into_iter().flatten().nth(slot)
+    }
+
+    /// Takes the coin above a slot of the points-card row, if any.
+    pub fn take(&mut self, slot: usize) -> Option<Coin> {
+        let coin = self.coin_for_slot(slot)?;
+        match coin {
+            Coin::Gold => self.gold -= 1,
+            Coin::Silver => self.silver -= 1,
+        }
+        Some(coin)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A face-up action card in the merchant row, with the spices players have left on it.
+pub struct MerchantSlot {
+    pub card: ActionCard,
+    pub spices: SpiceAmount,
+}
+
+impl MerchantSlot {
+    /// Creates a slot holding the given card and no spices.
+    pub fn new(card: ActionCard) -> Self {
+        Self {
+            card,
+            spices: SpiceAmount::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state of a whole game: the decks, the two market rows and the players.
+///
+/// The top of each deck is its first card. When a card is taken from a row, the cards to its right slide left and the
+/// top card of the deck fills the last slot.
+pub struct Game {
+    seed: Option<u64>,
+    points_deck: Vec<PointsCard>,
+    points_row: Vec<PointsCard>,
+    merchant_deck: Vec<ActionCard>,
+    merchant_row: Vec<MerchantSlot>,
+    coins: CoinSupply,
+    players: Vec<Player>,
+    current_player: usize,
+    end_triggered: bool,
+    history: Vec<PlayerAction>,
+}
+
+impl Game {
+    /// Sets up a game for the given number of players, shuffling the full decks with the bundled [`Rng`].
+    ///
+    /// The same seed always deals the same game, so a seed and the actions played are enough to reproduce it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::Game;
+    /// let game = Game::new(4, 2024).unwrap();
+    /// assert_eq!(game, Game::new(4, 2024).unwrap());
+    /// assert_ne!(game, Game::new(4, 2025).unwrap());
+    /// assert_eq!(game.get_seed(), Some(2024));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidNumberOfPlayers` if the number of players is not between [`MIN_PLAYERS`] and [`MAX_PLAYERS`].
+    pub fn new(num_players: usize, seed: u64) -> Result<Self, GameErrors> {
+        let mut rng = Rng::new(seed);
+        let mut points_deck = POINTS_CARDS.to_vec();
+        rng.shuffle(&mut points_deck);
+        let mut merchant_deck = PURCHASABLE_ACTION_CARDS.to_vec();
+        rng.shuffle(&mut merchant_deck);
+
+        let mut game = Self::with_decks(num_players, points_deck, merchant_deck)?;
+        game.seed = Some(seed);
+        Ok(game)
+    }
+
+    /// Sets up a game for the given number of players using already shuffled decks.
+    ///
+    /// Each player gets the starting hand and the caravan of their seat in [`STARTING_CARAVANS`], and the rows are
+    /// dealt from the top of the decks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, game::Game};
+    /// let game = Game::with_decks(3, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+    /// assert_eq!(game.get_players().len(), 3);
+    /// assert_eq!(game.get_points_row(), &POINTS_CARDS[..5]);
+    /// assert_eq!(game.get_merchant_row()[0].card, PURCHASABLE_ACTION_CARDS[0]);
+    /// assert_eq!(game.get_players()[1].get_caravan().get_spice_amount(), spice_amount!(4, 0, 0, 0));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidNumberOfPlayers` if the number of players is not between [`MIN_PLAYERS`] and [`MAX_PLAYERS`].
+    ///
+    /// ```
+    /// use libcsr::{cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, errors::GameErrors, game::Game};
+    /// let result = Game::with_decks(6, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec());
+    /// assert_eq!(result, Err(GameErrors::InvalidNumberOfPlayers(6)));
+    /// ```
+    pub fn with_decks(
+        num_players: usize,
+        mut points_deck: Vec<PointsCard>,
+        mut merchant_deck: Vec<ActionCard>,
+    ) -> Result<Self, GameErrors> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&num_players) {
+            return Err(GameErrors::InvalidNumberOfPlayers(num_players));
+        }
+
+        let players = STARTING_CARAVANS[..num_players]
+            .iter()
+            .enumerate()
+            .map(|(idx, spice_amount)| {
+                Caravan::from_spice_amount(*spice_amount)
+                    .map(|caravan| Player::new(idx as u8 + 1, caravan))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let points_row = points_deck
+            .drain(..POINTS_ROW_SIZE.min(points_deck.len()))
+            .collect();
+        let merchant_row = merchant_deck
+            .drain(..MERCHANT_ROW_SIZE.min(merchant_deck.len()))
+            .map(MerchantSlot::new)
+            .collect();
+
+        Ok(Self {
+            seed: None,
+            points_deck,
+            points_row,
+            merchant_deck,
+            merchant_row,
+            coins: CoinSupply::new(num_players),
+            players,
+            current_player: 0,
+            end_triggered: false,
+            history: Vec::new(),
+        })
+    }
+
+    /// Get the seed the decks were shuffled with, or `None` if the game was set up with [`Game::with_decks`].
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Get every action played so far, in order.
+    pub fn get_history(&self) -> &[PlayerAction] {
+        &self.history
+    }
+
+    /// Get the players, in turn order.
+    pub fn get_players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Get the index in [`Game::get_players`] of the player whose turn it is.
+    pub fn get_current_player_index(&self) -> usize {
+        self.current_player
+    }
+
+    /// Get the player whose turn it is.
+    pub fn get_current_player(&self) -> &Player {
+        &self.players[self.current_player]
+    }
+
+    /// Get the face-up points cards, from left to right.
+    pub fn get_points_row(&self) -> &[PointsCard] {
+        &self.points_row
+    }
+
+    /// Get the face-up action cards and the spices on them, from left to right.
+    pub fn get_merchant_row(&self) -> &[MerchantSlot] {
+        &self.merchant_row
+    }
+
+    /// Get the number of points cards a player must claim to trigger the end of the game: 5 in 2–3 player games and 6
+    /// in 4–5 player games.
+    pub fn end_threshold(&self) -> usize {
+        if self.players.len() <= 3 {
+            5
+        } else {
+            6
+        }
+    }
+
+    /// Check if a player has reached [`Game::end_threshold`], making the current round the last one.
+    pub fn is_end_triggered(&self) -> bool {
+        self.end_triggered
+    }
+
+    /// Check if the game is over: the end was triggered and the round has been completed, so every player has taken
+    /// the same number of turns.
+    pub fn is_over(&self) -> bool {
+        self.end_triggered && self.current_player == 0
+    }
+
+    /// Get the coins left above the points-card row.
+    pub fn get_coins(&self) -> &CoinSupply {
+        &self.coins
+    }
+
+    /// Get the number of cards left in the points-card deck.
+    pub fn points_deck_len(&self) -> usize {
+        self.points_deck.len()
+    }
+
+    /// Get the number of cards left in the merchant deck.
+    pub fn merchant_deck_len(&self) -> usize {
+        self.merchant_deck.len()
+    }
+
+    /// Plays an action for the current player and passes the turn to the next player.
+    ///
+    /// If the action leaves the player's caravan overflowing, the turn only passes once they discard the excess with
+    /// [`PlayerAction::Discard`].
+    ///
+    /// Acquired action cards and claimed points cards must be in their market row; the row is refilled afterwards.
+    /// The game is left untouched if the action is illegal.
+    ///
+    /// Acquiring an action card requires paying one spice for each card to its left. The payment is placed on those
+    /// cards from left to right, least valuable spices first, and the spices on the acquired card go to the player's
+    /// caravan. This simplifies the printed rules, which let the player choose which cube goes on which card: the
+    /// payment stays an unordered [`SpiceAmount`], which keeps the number of legal acquisitions small.
+    ///
+    /// Claiming one of the first two points cards also awards the coin above its slot, see [`CoinSupply`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, game::Game, player::PlayerAction};
+    /// let mut game = Game::with_decks(2, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+    /// game.play(PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[2], spice_amount!(2, 0, 0, 0))).unwrap();
+    /// assert_eq!(game.get_players()[0].get_hand().last(), Some(&PURCHASABLE_ACTION_CARDS[2]));
+    /// assert_eq!(game.get_players()[0].get_caravan().get_spice_amount(), spice_amount!(1, 0, 0, 0));
+    /// assert_eq!(game.get_merchant_row()[1].spices, spice_amount!(1, 0, 0, 0));
+    /// assert_eq!(game.get_merchant_row()[5].card, PURCHASABLE_ACTION_CARDS[6]);
+    /// assert_eq!(game.get_current_player_index(), 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::GameOver` if the game has already ended.
+    /// * `GameErrors::CardNotInMarket` if the card to acquire or claim is not face-up.
+    /// * `GameErrors::InvalidAcquisitionPayment` if the payment is not one spice per card skipped.
+    /// * Any of the errors of [`PlayerAction::play`].
+    ///
+    /// ```
+    /// use libcsr::{cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, errors::GameErrors, game::Game, player::PlayerAction};
+    /// let mut game = Game::with_decks(2, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+    /// let result = game.play(PlayerAction::Score(POINTS_CARDS[35]));
+    /// assert_eq!(result, Err(GameErrors::CardNotInMarket));
+    /// ```
+    pub fn play(&mut self, action: PlayerAction) -> Result<(), GameErrors> {
+        if self.is_over() {
+            return Err(GameErrors::GameOver);
+        }
+
+        let end_threshold = self.end_threshold();
+        let player = &mut self.players[self.current_player];
+
+        match action {
+            PlayerAction::AcquireCard(card, payment) => {
+                let idx = self
+                    .merchant_row
+                    .iter()
+                    .position(|x| x.card == card)
+                    .ok_or(GameErrors::CardNotInMarket)?;
+                if payment.total() != idx {
+                    return Err(GameErrors::InvalidAcquisitionPayment(idx));
+                }
+                action.play(player)?;
+
+                for (slot, cube) in self.merchant_row.iter_mut().zip(payment.cubes()) {
+                    slot.spices = slot.spices.add(&SpiceAmount::from(cube));
+                }
+
+                let slot = self.merchant_row.remove(idx);
+                player.get_caravan_mut().add(&slot.spices);
+                if !self.merchant_deck.is_empty() {
+                    self.merchant_row
+                        .push(MerchantSlot::new(self.merchant_deck.remove(0)));
+                }
+            }
+            PlayerAction::Score(card) => {
+                let idx = self
+                    .points_row
+                    .iter()
+                    .position(|x| *x == card)
+                    .ok_or(GameErrors::CardNotInMarket)?;
+                action.play(player)?;
+                if let Some(coin) = self.coins.take(idx) {
+                    player.add_coin(coin);
+                }
+                self.points_row.remove(idx);
+                if !self.points_deck.is_empty() {
+                    self.points_row.push(self.points_deck.remove(0));
+                }
+                if player.get_score_pile().len() >= end_threshold {
+                    self.end_triggered = true;
+                }
+            }
+            _ => action.play(player)?,
+        }
+
+        if !player.get_caravan().is_overflowing() {
+            self.current_player = (self.current_player + 1) % self.players.len();
+        }
+        self.history.push(action);
+        Ok(())
+    }
+    /// Checks that the spices an action moves fit the game, without playing it: an acquisition pays at most one cube
+    /// per card that can be skipped, and a discard names at most the cubes in the current player's caravan. Servers
+    /// run this on the actions of their clients before [`Game::play`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, errors::GameErrors, game::Game, player::PlayerAction};
+    /// let game = Game::with_decks(2, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+    /// let payment = spice_amount!(200, 57, 0, 0);
+    /// let action = PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[2], payment);
+    /// assert_eq!(game.check_limits(&action), Err(GameErrors::TooManySpices(5)));
+    /// assert_eq!(game.check_limits(&PlayerAction::Rest), Ok(()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::TooManySpices` if the action moves more cubes than the merchant row or the caravan allows.
+    pub fn check_limits(&self, action: &PlayerAction) -> Result<(), GameErrors> {
+        let (spices, limit) = match action {
+            PlayerAction::AcquireCard(_, payment) => {
+                (payment, self.merchant_row.len().saturating_sub(1))
+            }
+            PlayerAction::Discard(discard) => (
+                discard,
+                self.get_current_player().get_caravan().current_capacity(),
+            ),
+            _ => return Ok(()),
+        };
+        match spices.total() > limit {
+            true => Err(GameErrors::TooManySpices(limit)),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::action::PURCHASABLE_ACTION_CARDS;
+    use crate::cards::points::POINTS_CARDS;
+    use crate::cards::{ActionCard, CardChoice};
+    use crate::errors::GameErrors;
+    use crate::game::{Coin, CoinSupply, Game, MERCHANT_ROW_SIZE, POINTS_ROW_SIZE};
+    use crate::player::PlayerAction;
+    use crate::spice::SpiceCube;
+    use crate::{points_card, spice_amount};
+
+    fn new_game(num_players: usize) -> Game {
+        Game::with_decks(
+            num_players,
+            POINTS_CARDS.to_vec(),
+            PURCHASABLE_ACTION_CARDS.to_vec(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_setup() {
+        let game = new_game(5);
+        let caravans: Vec<_> = game
+            .get_players()
+            .iter()
+            .map(|player| player.get_caravan().get_spice_amount())
+            .collect();
+        assert_eq!(
+            caravans,
+            vec![
+                spice_amount!(3, 0, 0, 0),
+                spice_amount!(4, 0, 0, 0),
+                spice_amount!(4, 0, 0, 0),
+                spice_amount!(3, 1, 0, 0),
+                spice_amount!(3, 1, 0, 0),
+            ]
+        );
+        let orders: Vec<_> = game
+            .get_players()
+            .iter()
+            .map(|player| player.get_player_order())
+            .collect();
+        assert_eq!(orders, vec![1, 2, 3, 4, 5]);
+        assert_eq!(game.points_deck_len(), POINTS_CARDS.len() - POINTS_ROW_SIZE);
+        assert_eq!(
+            game.merchant_deck_len(),
+            PURCHASABLE_ACTION_CARDS.len() - MERCHANT_ROW_SIZE
+        );
+    }
+
+    #[test]
+    fn test_seeded_games_are_reproducible() {
+        let mut first = Game::new(3, 99).unwrap();
+        let mut second = Game::new(3, 99).unwrap();
+
+        for turn in 0..30 {
+            let actions = first.legal_actions();
+            let action = actions[turn % actions.len()].clone();
+            first.play(action.clone()).unwrap();
+            second.play(action).unwrap();
+        }
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_invalid_number_of_players() {
+        let result = Game::with_decks(1, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec());
+        assert_eq!(result, Err(GameErrors::InvalidNumberOfPlayers(1)));
+    }
+
+    #[test]
+    fn test_turn_order_wraps() {
+        let mut game = new_game(2);
+        let gain = PlayerAction::PlayCard(
+            ActionCard::Gain(spice_amount!(2, 0, 0, 0)),
+            CardChoice::Gain,
+        );
+
+        game.play(gain.clone()).unwrap();
+        assert_eq!(game.get_current_player_index(), 1);
+        game.play(gain).unwrap();
+        assert_eq!(game.get_current_player_index(), 0);
+        assert_eq!(
+            game.get_current_player().get_caravan().get_spice_amount(),
+            spice_amount!(5, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_acquire_collects_deposited_spices() {
+        let mut game = new_game(2);
+        game.play(PlayerAction::AcquireCard(
+            PURCHASABLE_ACTION_CARDS[3],
+            spice_amount!(3, 0, 0, 0),
+        ))
+        .unwrap();
+        game.play(PlayerAction::AcquireCard(
+            PURCHASABLE_ACTION_CARDS[2],
+            spice_amount!(2, 0, 0, 0),
+        ))
+        .unwrap();
+
+        // The second player skipped two cards and took the third, each holding one turmeric.
+        assert_eq!(
+            game.get_players()[1].get_caravan().get_spice_amount(),
+            spice_amount!(3, 0, 0, 0)
+        );
+        let deposits: Vec<_> = game
+            .get_merchant_row()
+            .iter()
+            .map(|slot| slot.spices)
+            .collect();
+        assert_eq!(
+            deposits,
+            vec![
+                spice_amount!(2, 0, 0, 0),
+                spice_amount!(2, 0, 0, 0),
+                spice_amount!(0, 0, 0, 0),
+                spice_amount!(0, 0, 0, 0),
+                spice_amount!(0, 0, 0, 0),
+                spice_amount!(0, 0, 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_acquire_wrong_payment() {
+        let mut game = new_game(2);
+        let before = game.clone();
+
+        assert_eq!(
+            game.play(PlayerAction::AcquireCard(
+                PURCHASABLE_ACTION_CARDS[2],
+                spice_amount!(1, 0, 0, 0)
+            )),
+            Err(GameErrors::InvalidAcquisitionPayment(2))
+        );
+        assert_eq!(
+            game.play(PlayerAction::AcquireCard(
+                PURCHASABLE_ACTION_CARDS[4],
+                spice_amount!(4, 0, 0, 0)
+            )),
+            Err(GameErrors::CannotSubtractSpiceAmount(
+                spice_amount!(3, 0, 0, 0),
+                spice_amount!(1, 0, 0, 0)
+            ))
+        );
+        assert_eq!(game, before);
+    }
+
+    #[test]
+    fn test_coins_slip_when_gold_runs_out() {
+        let mut supply = CoinSupply::new(2);
+        assert_eq!(supply, CoinSupply { gold: 4, silver: 4 });
+
+        for _ in 0..4 {
+            assert_eq!(supply.take(0), Some(Coin::Gold));
+        }
+        assert_eq!(supply.take(1), None);
+        assert_eq!(supply.take(0), Some(Coin::Silver));
+        assert_eq!(supply, CoinSupply { gold: 0, silver: 3 });
+    }
+
+    #[test]
+    fn test_score_awards_coin() {
+        let points_deck = vec![
+            points_card!(7, [3, 0, 0, 0]),
+            points_card!(8, [0, 4, 0, 0]),
+            points_card!(9, [4, 0, 0, 0]),
+            points_card!(8, [2, 3, 0, 0]),
+            points_card!(8, [2, 0, 2, 0]),
+            points_card!(9, [3, 0, 2, 0]),
+        ];
+        let mut game =
+            Game::with_decks(2, points_deck.clone(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+        game.play(PlayerAction::Score(points_deck[0])).unwrap();
+        game.play(PlayerAction::Score(points_deck[2])).unwrap();
+
+        let [first, second] = game.get_players() else {
+            panic!("expected two players");
+        };
+        assert_eq!(first.get_coins(), &[Coin::Gold]);
+        assert_eq!(first.score(), 10);
+        assert_eq!(second.get_coins(), &[Coin::Silver]);
+        assert_eq!(second.score(), 10);
+        assert_eq!(game.get_coins(), &CoinSupply { gold: 3, silver: 3 });
+        assert_eq!(game.get_points_row().len(), 4);
+    }
+
+    #[test]
+    fn test_overflow_keeps_turn_until_discard() {
+        let mut game = new_game(2);
+        let gain = PlayerAction::PlayCard(
+            ActionCard::Gain(spice_amount!(2, 0, 0, 0)),
+            CardChoice::Gain,
+        );
+        let upgrade = PlayerAction::PlayCard(
+            ActionCard::Upgrade(2),
+            CardChoice::Upgrade(vec![(SpiceCube::Turmeric, 2)]),
+        );
+        for _ in 0..3 {
+            for action in [gain.clone(), upgrade.clone(), PlayerAction::Rest] {
+                game.play(action.clone()).unwrap();
+                game.play(action).unwrap();
+            }
+        }
+
+        // The first player holds 6 turmeric and 3 cardamon before gaining 2 more turmeric.
+        game.play(gain).unwrap();
+        assert_eq!(game.get_current_player_index(), 0);
+        assert_eq!(
+            game.play(PlayerAction::Rest),
+            Err(GameErrors::DiscardPending(1))
+        );
+        game.play(PlayerAction::Discard(spice_amount!(0, 0, 1, 0)))
+            .unwrap();
+        assert_eq!(game.get_current_player_index(), 1);
+        assert_eq!(
+            game.get_players()[0].get_caravan().get_spice_amount(),
+            spice_amount!(8, 0, 2, 0)
+        );
+    }
+
+    #[test]
+    fn test_illegal_action_keeps_turn() {
+        let mut game = new_game(2);
+        let before = game.clone();
+
+        assert_eq!(
+            game.play(PlayerAction::Rest),
+            Err(GameErrors::EmptyDiscardPile)
+        );
+        assert_eq!(
+            game.play(PlayerAction::Score(POINTS_CARDS[0])),
+            Err(GameErrors::CannotSubtractSpiceAmount(
+                spice_amount!(3, 0, 0, 0),
+                spice_amount!(0, 2, 0, 0)
+            ))
+        );
+        assert_eq!(
+            game.play(PlayerAction::Discard(spice_amount!(0, 0, 0, 0))),
+            Err(GameErrors::NothingToDiscard)
+        );
+        assert_eq!(game, before);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::game::Game;
+    use serde_json::{Map, Value};
+
+    const SCHEMA: &str = include_str!("../../schema/game-state.schema.json");
+
+    /// Checks `value` against the subset of JSON Schema used by `schema/game-state.schema.json`.
+    fn validate(value: &Value, schema: &Value, root: &Value) -> Result<(), String> {
+        let schema = schema.as_object().unwrap();
+        if let Some(Value::String(reference)) = schema.get("$ref") {
+            let name = reference.trim_start_matches("#/$defs/");
+            return validate(value, &root["$defs"][name], root);
+        }
+        if let Some(Value::Array(options)) = schema.get("oneOf") {
+            let matches = options
+                .iter()
+                .filter(|option| validate(value, option, root).is_ok())
+                .count();
+            return (matches == 1)
+                .then_some(())
+                .ok_or(format!("{value} matches {matches} options"));
+        }
+        if let Some(Value::Array(options)) = schema.get("enum") {
+            return options
+                .contains(value)
+                .then_some(())
+                .ok_or(format!("{value} is not one of {options:?}"));
+        }
+
+        let types: Vec<_> = match schema.get("type") {
+            Some(Value::String(name)) => vec![name.as_str()],
+            Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let type_matches = types.iter().any(|name| match *name {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "integer" => value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => false,
+        });
+        if !type_matches {
+            return Err(format!("{value} is not of type {types:?}"));
+        }
+
+        let limit = |key: &str| schema.get(key).and_then(Value::as_u64);
+        match value {
+            Value::Object(object) => validate_object(object, schema, root),
+            Value::Array(items) => {
+                if limit("minItems").is_some_and(|min| (items.len() as u64) < min)
+                    || limit("maxItems").is_some_and(|max| (items.len() as u64) > max)
+                {
+                    return Err(format!("{value} has the wrong number of items"));
+                }
+                for (idx, item) in items.iter().enumerate() {
+                    let item_schema = match schema.get("prefixItems") {
+                        Some(Value::Array(prefix)) => &prefix[idx],
+                        _ => &schema["items"],
+                    };
+                    validate(item, item_schema, root)?;
+                }
+                Ok(())
+            }
+            Value::Number(number) => {
+                let number = number.as_u64().unwrap();
+                if limit("minimum").is_some_and(|min| number < min)
+                    || limit("maximum").is_some_and(|max| number > max)
+                {
+                    return Err(format!("{number} is out of range"));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_object(
+        object: &Map<String, Value>,
+        schema: &Map<String, Value>,
+        root: &Value,
+    ) -> Result<(), String> {
+        let properties = schema["properties"].as_object().unwrap();
+        for required in schema["required"].as_array().unwrap() {
+            if !object.contains_key(required.as_str().unwrap()) {
+                return Err(format!("missing {required}"));
+            }
+        }
+        for (key, value) in object {
+            let property = properties
+                .get(key)
+                .ok_or(format!("unexpected property {key}"))?;
+            validate(value, property, root).map_err(|error| format!("{key}: {error}"))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_matches_schema_and_round_trips() {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        let mut game = Game::new(3, 5).unwrap();
+
+        for turn in 0..40 {
+            let actions = game.legal_actions();
+            game.play(actions[turn * 7 % actions.len()].clone())
+                .unwrap();
+
+            let value = serde_json::to_value(&game).unwrap();
+            assert_eq!(validate(&value, &schema, &schema), Ok(()));
+            assert_eq!(serde_json::from_value::<Game>(value).unwrap(), game);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::Game;
+use crate::cards::{ActionCard, CardChoice};
+use crate::player::PlayerAction;
+
+impl Game {
+    /// Lists every legal action for the current player, each of which can be passed directly to [`Game::play`].
+    ///
+    /// When the player's caravan is overflowing, only the possible discards are listed. Otherwise the list holds, in
+    /// order:
+    ///
+    /// * Every card in hand, with each possible number of exchanges or distinct upgrade result.
+    /// * Every affordable merchant-row card, with each distinct payment for the cards it skips.
+    /// * Resting, if the discard pile is not empty.
+    /// * Every points card whose cost the caravan contains.
+    ///
+    /// No actions are listed once the game is over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, game::Game};
+    /// let mut game = Game::with_decks(2, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+    /// let actions = game.legal_actions();
+    /// assert!(!actions.is_empty());
+    /// for action in actions {
+    ///     game.clone().play(action).unwrap();
+    /// }
+    /// ```
+    pub fn legal_actions(&self) -> Vec<PlayerAction> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        let player = self.get_current_player();
+        let caravan = player.get_caravan();
+        let spice_amount = caravan.get_spice_amount();
+
+        if caravan.is_overflowing() {
+            return spice_amount
+                .selections(caravan.excess())
+                .into_iter()
+                .map(PlayerAction::Discard)
+                .collect();
+        }
+
+        let mut actions = Vec::new();
+
+        let hand = player.get_hand();
+        for (idx, card) in hand.iter().enumerate() {
+            if hand[..idx].contains(card) {
+                continue;
+            }
+
+            match card {
+                ActionCard::Gain(_) => {
+                    actions.push(PlayerAction::PlayCard(*card, CardChoice::Gain));
+                }
+                ActionCard::Exchange(input, _) => {
+                    let times = (1..)
+                        .take_while(|times| {
+                            input
+                                .multiply(*times)
+                                .is_ok_and(|cost| spice_amount.contains(&cost))
+                        })
+                        .map(|times| PlayerAction::PlayCard(*card, CardChoice::Exchange(times)));
+                    actions.extend(times);
+                }
+                ActionCard::Upgrade(budget) => {
+                    let upgrades = caravan
+                        .upgrade_options(*budget)
+                        .into_iter()
+                        .map(|(plan, _)| PlayerAction::PlayCard(*card, CardChoice::Upgrade(plan)));
+                    actions.extend(upgrades);
+                }
+            }
+        }
+
+        for (idx, slot) in self.merchant_row.iter().enumerate() {
+            let payments = spice_amount
+                .selections(idx)
+                .into_iter()
+                .map(|payment| PlayerAction::AcquireCard(slot.card, payment));
+            actions.extend(payments);
+        }
+
+        if !player.get_discard_pile().is_empty() {
+            actions.push(PlayerAction::Rest);
+        }
+
+        let scores = self
+            .points_row
+            .iter()
+            .filter(|card| spice_amount.contains(&card.cost))
+            .map(|card| PlayerAction::Score(*card));
+        actions.extend(scores);
+
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::action::PURCHASABLE_ACTION_CARDS;
+    use crate::cards::points::POINTS_CARDS;
+    use crate::cards::{ActionCard, CardChoice};
+    use crate::game::Game;
+    use crate::player::PlayerAction;
+    use crate::spice::SpiceCube::Turmeric;
+    use crate::spice_amount;
+
+    fn new_game() -> Game {
+        Game::with_decks(2, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_opening_actions() {
+        let game = new_game();
+        let upgrade =
+            |plan| PlayerAction::PlayCard(ActionCard::Upgrade(2), CardChoice::Upgrade(plan));
+
+        assert_eq!(
+            game.legal_actions(),
+            vec![
+                PlayerAction::PlayCard(
+                    ActionCard::Gain(spice_amount!(2, 0, 0, 0)),
+                    CardChoice::Gain
+                ),
+                upgrade(vec![(Turmeric, 1)]),
+                upgrade(vec![(Turmeric, 1), (Turmeric, 1)]),
+                upgrade(vec![(Turmeric, 2)]),
+                PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[0], spice_amount!(0, 0, 0, 0)),
+                PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[1], spice_amount!(1, 0, 0, 0)),
+                PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[2], spice_amount!(2, 0, 0, 0)),
+                PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[3], spice_amount!(3, 0, 0, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_listed_action_is_legal() {
+        let mut game = new_game();
+
+        // Always take the last listed action, which exercises acquisitions, rests and scores.
+        for _ in 0..60 {
+            let actions = game.legal_actions();
+            if actions.is_empty() {
+                break;
+            }
+            for action in &actions {
+                assert_eq!(game.clone().play(action.clone()), Ok(()), "{action:?}");
+            }
+            game.play(actions.last().unwrap().clone()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_overflow_lists_discards() {
+        let mut game = new_game();
+        let gain = PlayerAction::PlayCard(
+            ActionCard::Gain(spice_amount!(2, 0, 0, 0)),
+            CardChoice::Gain,
+        );
+        let upgrade = PlayerAction::PlayCard(
+            ActionCard::Upgrade(2),
+            CardChoice::Upgrade(vec![(Turmeric, 1)]),
+        );
+        let gain_three = PlayerAction::PlayCard(PURCHASABLE_ACTION_CARDS[0], CardChoice::Gain);
+        let actions = [
+            PlayerAction::AcquireCard(PURCHASABLE_ACTION_CARDS[0], spice_amount!(0, 0, 0, 0)),
+            gain.clone(),
+            gain_three.clone(),
+            upgrade,
+            gain.clone(),
+            PlayerAction::Rest,
+            PlayerAction::Rest,
+            gain,
+            gain_three,
+        ];
+        for action in actions {
+            game.play(action).unwrap();
+        }
+
+        // The first player gained 3 + 2 + 3 turmeric over their 3 starting ones.
+        assert_eq!(
+            game.legal_actions(),
+            vec![PlayerAction::Discard(spice_amount!(1, 0, 0, 0))]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use super::Game;
+use crate::errors::GameErrors;
+use crate::player::PlayerAction;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A complete record of a game: the seed it was set up from, the seated players and every action played.
+///
+/// A record is written as plain text, with a `seed` line, one `seat` line per player and one line per action, each
+/// prefixed with its turn number and the seat of the player taking it:
+///
+/// ```text
+/// seed 42
+/// seat 1 Alice
+/// seat 2 Bob
+/// 1 P1 play YY
+/// 2 P2 acquire YYY>GG Y
+/// 3 P1 play U2 Y+1 Y+1
+/// 4 P2 play YYY>GG x1
+/// 5 P1 rest
+/// 6 P2 score 8:YYGG
+/// ```
+///
+/// Actions are written in the notation of [`PlayerAction`]. Discards after an overflowing action belong to the same
+/// turn. Blank lines and lines starting with `#` are ignored.
+pub struct GameRecord {
+    /// Seed the game was set up from, see [`Game::new`].
+    pub seed: u64,
+    /// Names of the players, in turn order.
+    pub seats: Vec<String>,
+    /// Every action played, in order.
+    pub actions: Vec<PlayerAction>,
+}
+
+impl GameRecord {
+    /// Records a game played so far, naming the players `Player 1`, `Player 2`, etc.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::{Game, record::GameRecord};
+    /// let mut game = Game::new(2, 42).unwrap();
+    /// game.play(game.legal_actions()[0].clone()).unwrap();
+    /// let record = GameRecord::from_game(&game).unwrap();
+    /// assert_eq!(record.seats, ["Player 1", "Player 2"]);
+    /// assert_eq!(record.replay(), Ok(game));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::UnseededGame` if the game was not set up from a seed, as it could not be replayed.
+    pub fn from_game(game: &Game) -> Result<Self, GameErrors> {
+        Ok(Self {
+            seed: game.get_seed().ok_or(GameErrors::UnseededGame)?,
+            seats: (1..=game.get_players().len())
+                .map(|seat| format!("Player {seat}"))
+                .collect(),
+            actions: game.get_history().to_vec(),
+        })
+    }
+
+    /// Sets up a game from the seed and plays every recorded action, checking that each one is legal.
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::InvalidNumberOfPlayers` if the number of seats is not supported.
+    /// * `GameErrors::IllegalMove` for the first action that cannot be played, with its turn number.
+    ///
+    /// ```
+    /// use libcsr::{errors::GameErrors, game::record::GameRecord};
+    /// let record: GameRecord = "seed 42\nseat 1 Alice\nseat 2 Bob\n1 P1 rest\n".parse().unwrap();
+    /// let result = record.replay();
+    /// assert!(matches!(result, Err(GameErrors::IllegalMove(1, _, _))));
+    /// ```
+    pub fn replay(&self) -> Result<Game, GameErrors> {
+        let mut game = Game::new(self.seats.len(), self.seed)?;
+        for (turn, _, action) in turns(&self.actions, self.seats.len()) {
+            game.play(action.clone()).map_err(|error| {
+                GameErrors::IllegalMove(turn, action.to_string(), Box::new(error))
+            })?;
+        }
+        Ok(game)
+    }
+}
+
+/// Numbers the actions with their turn and the seat, starting at 1, of the player taking them.
+fn turns(
+    actions: &[PlayerAction],
+    seats: usize,
+) -> impl Iterator<Item = (usize, usize, &PlayerAction)> {
+    let mut turn = 0;
+    actions.iter().map(move |action| {
+        if turn == 0 || !matches!(action, PlayerAction::Discard(_)) {
+            turn += 1;
+        }
+        (turn, (turn - 1) % seats.max(1) + 1, action)
+    })
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        for (idx, name) in self.seats.iter().enumerate() {
+            writeln!(f, "seat {} {name}", idx + 1)?;
+        }
+        for (turn, seat, action) in turns(&self.actions, self.seats.len()) {
+            writeln!(f, "{turn} P{seat} {action}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = GameErrors;
+
+    /// Parses a record written by its `Display` implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidRecord` with the line number of the first line that cannot be parsed, or whose
+    /// turn number or seat does not follow from the previous actions.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seed = None;
+        let mut seats = Vec::new();
+        let mut actions = Vec::new();
+        let mut numbering = Vec::new();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_number = idx + 1;
+            let invalid = |message: String| GameErrors::InvalidRecord(line_number, message);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match keyword {
+                "seed" if seed.is_none() && seats.is_empty() => {
+                    seed = Some(
+                        rest.trim()
+                            .parse()
+                            .map_err(|_| invalid(format!("invalid seed {rest:?}")))?,
+                    );
+                }
+                "seat" if seed.is_some() && actions.is_empty() => {
+                    let (seat, name) = rest.trim().split_once(' ').unwrap_or((rest, ""));
+                    if seat != (seats.len() + 1).to_string() {
+                        return Err(invalid(format!("expected seat {}", seats.len() + 1)));
+                    }
+                    if name.trim().is_empty() {
+                        return Err(invalid(format!("missing name for seat {seat}")));
+                    }
+                    seats.push(name.trim().to_string());
+                }
+                _ if seed.is_some() && !seats.is_empty() => {
+                    let mut tokens = line.split_whitespace();
+                    let turn = tokens.next().and_then(|turn| turn.parse::<usize>().ok());
+                    let seat = tokens
+                        .next()
+                        .and_then(|seat| seat.strip_prefix('P'))
+                        .and_then(|seat| seat.parse::<usize>().ok());
+                    let (Some(turn), Some(seat)) = (turn, seat) else {
+                        return Err(invalid(format!(
+                            "expected \"<turn> P<seat> <action>\", got {line:?}"
+                        )));
+                    };
+                    let action = tokens
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .parse()
+                        .map_err(|error: GameErrors| invalid(error.to_string()))?;
+                    actions.push(action);
+                    numbering.push((line_number, turn, seat));
+                }
+                _ => return Err(invalid(format!("unexpected line {line:?}"))),
+            }
+        }
+
+        let seed = seed.ok_or(GameErrors::InvalidRecord(0, "missing seed".to_string()))?;
+        for ((line_number, turn, seat), (expected_turn, expected_seat, _)) in
+            numbering.into_iter().zip(turns(&actions, seats.len()))
+        {
+            if (turn, seat) != (expected_turn, expected_seat) {
+                return Err(GameErrors::InvalidRecord(
+                    line_number,
+                    format!("expected turn {expected_turn} by P{expected_seat}"),
+                ));
+            }
+        }
+
+        Ok(Self {
+            seed,
+            seats,
+            actions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::GameErrors;
+    use crate::game::record::GameRecord;
+    use crate::game::Game;
+
+    fn played_game() -> Game {
+        let mut game = Game::new(3, 5).unwrap();
+        for turn in 0..60 {
+            let actions = game.legal_actions();
+            if actions.is_empty() {
+                break;
+            }
+            game.play(actions[turn * 7 % actions.len()].clone())
+                .unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let game = played_game();
+        let record = GameRecord::from_game(&game).unwrap();
+        let text = record.to_string();
+
+        assert!(text.starts_with("seed 5\nseat 1 Player 1\n"));
+        assert_eq!(text.parse::<GameRecord>(), Ok(record.clone()));
+        assert_eq!(record.replay(), Ok(game));
+    }
+
+    #[test]
+    fn test_replay_reports_first_illegal_move() {
+        let record: GameRecord =
+            "seed 1\nseat 1 Ann\nseat 2 Bo\n1 P1 play YY\n2 P2 play U2 Y+1\n3 P1 play YY\n"
+                .parse()
+                .unwrap();
+        match record.replay() {
+            Err(GameErrors::IllegalMove(3, action, reason)) => {
+                assert_eq!(action, "play YY");
+                assert_eq!(*reason, GameErrors::CardNotInHand);
+            }
+            result => panic!("unexpected replay result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "seed 1\nseat 1 Ann\nseat 2 Bo\n1 P1 play YQ\n".parse::<GameRecord>(),
+            Err(GameErrors::InvalidRecord(
+                4,
+                "Invalid notation \"YQ\" at column 2: 'Q' is not a spice, expected one of Y, R, G or B"
+                    .to_string()
+            ))
+        );
+        assert_eq!(
+            "seed 1\nseat 1 Ann\nseat 2 Bo\n1 P1 rest\n1 P2 rest\n".parse::<GameRecord>(),
+            Err(GameErrors::InvalidRecord(
+                5,
+                "expected turn 2 by P2".to_string()
+            ))
+        );
+        assert_eq!(
+            "seat 1 Ann\n".parse::<GameRecord>(),
+            Err(GameErrors::InvalidRecord(
+                1,
+                "unexpected line \"seat 1 Ann\"".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use super::{Game, MAX_PLAYERS, MERCHANT_ROW_SIZE, MIN_PLAYERS, POINTS_ROW_SIZE};
+use crate::errors::GameErrors;
+use std::fs;
+use std::path::Path;
+
+/// First word of every save file.
+pub const SAVE_MAGIC: &str = "csr-save";
+
+/// Version of the save format written by [`Game::save`].
+///
+/// Bump it whenever the serialized game state changes, so older saves are rejected instead of misloaded.
+pub const SAVE_FORMAT_VERSION: u32 = 3;
+
+/// Oldest version of the save format that can still be loaded. Version 2 saves have no seats line.
+pub const MIN_SAVE_FORMAT_VERSION: u32 = 2;
+
+impl Game {
+    /// Serializes the game into the save format: a `csr-save <version>` header line followed by the game state as
+    /// JSON, as described by `schema/game-state.schema.json`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::Game;
+    /// let game = Game::new(2, 7).unwrap();
+    /// let save = game.to_save_string().unwrap();
+    /// assert!(save.starts_with("csr-save 3\n"));
+    /// assert_eq!(Game::from_save_str(&save), Ok(game));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidSaveFile` if the game cannot be serialized.
+    pub fn to_save_string(&self) -> Result<String, GameErrors> {
+        self.to_save_string_with_seats(&[])
+    }
+
+    /// Serializes the game like [`Game::to_save_string`], followed by a line with a JSON array describing who plays
+    /// each seat, such as a player's name or a kind of bot. The save format does not interpret the seats; no line is
+    /// written when `seats` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::Game;
+    /// let game = Game::new(2, 7).unwrap();
+    /// let seats = vec!["ann".to_string(), "g".to_string()];
+    /// let save = game.to_save_string_with_seats(&seats).unwrap();
+    /// assert!(save.ends_with("\n[\"ann\",\"g\"]\n"));
+    /// assert_eq!(Game::from_save_str_with_seats(&save), Ok((game, seats)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidSaveFile` if the game cannot be serialized, or if `seats` is not empty and does not
+    /// name every seat of the game.
+    pub fn to_save_string_with_seats(&self, seats: &[String]) -> Result<String, GameErrors> {
+        let state = serde_json::to_string(self)
+            .map_err(|error| GameErrors::InvalidSaveFile(error.to_string()))?;
+        let mut save = format!("{SAVE_MAGIC} {SAVE_FORMAT_VERSION}\n{state}\n");
+        if !seats.is_empty() {
+            self.check_seats(seats)
+                .map_err(GameErrors::InvalidSaveFile)?;
+            let seats = serde_json::to_string(seats)
+                .map_err(|error| GameErrors::InvalidSaveFile(error.to_string()))?;
+            save.push_str(&format!("{seats}\n"));
+        }
+        Ok(save)
+    }
+
+    /// Deserializes a game written by [`Game::to_save_string`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::UnsupportedSaveVersion` if the save was written by another version of the format, or
+    /// `GameErrors::InvalidSaveFile` if it is not a save at all, its game state is malformed, or it describes a position no
+    /// game can reach, such as a player out of turn with an overflowing caravan.
+    ///
+    /// ```
+    /// use libcsr::{errors::GameErrors, game::Game};
+    /// let result = Game::from_save_str("csr-save 0\n{}\n");
+    /// assert_eq!(result, Err(GameErrors::UnsupportedSaveVersion(0)));
+    /// ```
+    pub fn from_save_str(save: &str) -> Result<Self, GameErrors> {
+        Self::from_save_str_with_seats(save).map(|(game, _)| game)
+    }
+
+    /// Deserializes a game and its seats written by [`Game::to_save_string_with_seats`]. The seats are empty if the
+    /// save does not describe them.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Game::from_save_str`], or `GameErrors::InvalidSaveFile` if the seats are malformed or
+    /// do not match the number of players.
+    pub fn from_save_str_with_seats(save: &str) -> Result<(Self, Vec<String>), GameErrors> {
+        let (header, state) = save.split_once('\n').unwrap_or((save, ""));
+        let version = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            [SAVE_MAGIC, version] => version.parse::<u32>().map_err(|_| {
+                GameErrors::InvalidSaveFile(format!("invalid format version {version:?}"))
+            })?,
+            _ => {
+                return Err(GameErrors::InvalidSaveFile(format!(
+                    "missing {SAVE_MAGIC:?} header"
+                )))
+            }
+        };
+        if !(MIN_SAVE_FORMAT_VERSION..=SAVE_FORMAT_VERSION).contains(&version) {
+            return Err(GameErrors::UnsupportedSaveVersion(version));
+        }
+
+        let (state, seats) = state.split_once('\n').unwrap_or((state, ""));
+        let game: Self = serde_json::from_str(state)
+            .map_err(|error| GameErrors::InvalidSaveFile(error.to_string()))?;
+        game.check_consistency()
+            .map_err(GameErrors::InvalidSaveFile)?;
+        let seats: Vec<String> = match seats.trim() {
+            "" => Vec::new(),
+            seats => serde_json::from_str(seats)
+                .map_err(|error| GameErrors::InvalidSaveFile(error.to_string()))?,
+        };
+        if !seats.is_empty() {
+            game.check_seats(&seats)
+                .map_err(GameErrors::InvalidSaveFile)?;
+        }
+        Ok((game, seats))
+    }
+
+    /// Checks that there is one seat per player.
+    fn check_seats(&self, seats: &[String]) -> Result<(), String> {
+        match seats.len() == self.players.len() {
+            true => Ok(()),
+            false => Err(format!(
+                "{} seats described for a {} player game",
+                seats.len(),
+                self.players.len()
+            )),
+        }
+    }
+
+    /// Checks the invariants that [`Game::play`] relies on, describing the first one that does not hold.
+    fn check_consistency(&self) -> Result<(), String> {
+        let players = self.players.len();
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+            return Err(format!(
+                "a game needs between {MIN_PLAYERS} and {MAX_PLAYERS} players, got {players}"
+            ));
+        }
+        if self.current_player >= players {
+            return Err(format!(
+                "current player {} does not exist in a {players} player game",
+                self.current_player
+            ));
+        }
+        if let Some(idx) = (0..players).find(|&idx| {
+            idx != self.current_player && self.players[idx].get_caravan().is_overflowing()
+        }) {
+            return Err(format!(
+                "player {} has an overflowing caravan out of turn",
+                idx + 1
+            ));
+        }
+        // Spices are counted in `u8`, so the player waiting to discard cannot have gained more than that.
+        let spices = self.players[self.current_player]
+            .get_caravan()
+            .current_capacity();
+        if spices > u8::MAX as usize {
+            return Err(format!(
+                "player {} holds {spices} spices, more than any turn can gain",
+                self.current_player + 1
+            ));
+        }
+
+        let rows = [
+            (
+                "points",
+                self.points_row.len(),
+                POINTS_ROW_SIZE,
+                self.points_deck.is_empty(),
+            ),
+            (
+                "merchant",
+                self.merchant_row.len(),
+                MERCHANT_ROW_SIZE,
+                self.merchant_deck.is_empty(),
+            ),
+        ];
+        for (name, len, size, deck_empty) in rows {
+            if len > size || (len < size && !deck_empty) {
+                return Err(format!(
+                    "the {name} row holds {len} cards instead of {size}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Saves the game to a file, see [`Game::to_save_string`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::SaveFileIo` if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GameErrors> {
+        fs::write(path, self.to_save_string()?)
+            .map_err(|error| GameErrors::SaveFileIo(error.to_string()))
+    }
+
+    /// Saves the game and its seats to a file, see [`Game::to_save_string_with_seats`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::SaveFileIo` if the file cannot be written, or any of the errors of
+    /// [`Game::to_save_string_with_seats`].
+    pub fn save_with_seats(
+        &self,
+        path: impl AsRef<Path>,
+        seats: &[String],
+    ) -> Result<(), GameErrors> {
+        fs::write(path, self.to_save_string_with_seats(seats)?)
+            .map_err(|error| GameErrors::SaveFileIo(error.to_string()))
+    }
+
+    /// Loads a game from a file written by [`Game::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::SaveFileIo` if the file cannot be read, or any of the errors of [`Game::from_save_str`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GameErrors> {
+        let save =
+            fs::read_to_string(path).map_err(|error| GameErrors::SaveFileIo(error.to_string()))?;
+        Self::from_save_str(&save)
+    }
+
+    /// Loads a game and its seats from a file written by [`Game::save_with_seats`], see
+    /// [`Game::from_save_str_with_seats`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::SaveFileIo` if the file cannot be read, or any of the errors of
+    /// [`Game::from_save_str_with_seats`].
+    pub fn load_with_seats(path: impl AsRef<Path>) -> Result<(Self, Vec<String>), GameErrors> {
+        let save =
+            fs::read_to_string(path).map_err(|error| GameErrors::SaveFileIo(error.to_string()))?;
+        Self::from_save_str_with_seats(&save)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::GameErrors;
+    use crate::game::Game;
+
+    #[test]
+    fn test_save_and_load_file() {
+        let mut game = Game::new(4, 31).unwrap();
+        for turn in 0..25 {
+            let actions = game.legal_actions();
+            game.play(actions[turn * 3 % actions.len()].clone())
+                .unwrap();
+        }
+
+        let path = std::env::temp_dir().join(format!("csr-save-test-{}.txt", std::process::id()));
+        game.save(&path).unwrap();
+        let loaded = Game::load(&path);
+        let seats: Vec<String> = ["ann", "g", "exec:python3 bot.py", "h"]
+            .map(String::from)
+            .to_vec();
+        game.save_with_seats(&path, &seats).unwrap();
+        let loaded_with_seats = Game::load_with_seats(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Ok(game.clone()));
+        assert_eq!(loaded_with_seats, Ok((game.clone(), seats)));
+
+        let old_save = game
+            .to_save_string()
+            .unwrap()
+            .replacen("csr-save 3", "csr-save 2", 1);
+        assert_eq!(
+            Game::from_save_str_with_seats(&old_save),
+            Ok((game, Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_other_files() {
+        assert!(matches!(
+            Game::from_save_str("{\"seed\": 1}"),
+            Err(GameErrors::InvalidSaveFile(_))
+        ));
+        assert!(matches!(
+            Game::from_save_str("csr-save 3\n{\"seed\": 1}"),
+            Err(GameErrors::InvalidSaveFile(_))
+        ));
+        assert_eq!(
+            Game::from_save_str("csr-save 1\n{}"),
+            Err(GameErrors::UnsupportedSaveVersion(1))
+        );
+        assert!(matches!(
+            Game::load("/nonexistent/csr.save"),
+            Err(GameErrors::SaveFileIo(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_saves() {
+        let save = Game::new(2, 5).unwrap().to_save_string().unwrap();
+        let tamper = |from: &str, to: &str| {
+            assert!(save.contains(from));
+            Game::from_save_str(&save.replacen(from, to, 1))
+        };
+
+        assert_eq!(
+            tamper("\"current_player\":0", "\"current_player\":9"),
+            Err(GameErrors::InvalidSaveFile(
+                "current player 9 does not exist in a 2 player game".to_string()
+            ))
+        );
+        assert_eq!(
+            tamper(
+                "\"caravan\":{\"turmeric\":4,",
+                "\"caravan\":{\"turmeric\":11,"
+            ),
+            Err(GameErrors::InvalidSaveFile(
+                "player 2 has an overflowing caravan out of turn".to_string()
+            ))
+        );
+        assert_eq!(
+            tamper(
+                "\"caravan\":{\"turmeric\":4,\"saffron\":0,",
+                "\"caravan\":{\"turmeric\":200,\"saffron\":100,"
+            ),
+            Err(GameErrors::InvalidSaveFile(
+                "player 2 has an overflowing caravan out of turn".to_string()
+            ))
+        );
+        assert_eq!(
+            tamper(
+                "\"caravan\":{\"turmeric\":3,\"saffron\":0,",
+                "\"caravan\":{\"turmeric\":200,\"saffron\":100,"
+            ),
+            Err(GameErrors::InvalidSaveFile(
+                "player 1 holds 300 spices, more than any turn can gain".to_string()
+            ))
+        );
+
+        let seats = vec!["ann".to_string()];
+        assert_eq!(
+            Game::from_save_str_with_seats(&format!("{save}[\"ann\"]\n")),
+            Err(GameErrors::InvalidSaveFile(
+                "1 seats described for a 2 player game".to_string()
+            ))
+        );
+        assert!(Game::new(2, 5)
+            .unwrap()
+            .to_save_string_with_seats(&seats)
+            .is_err());
+
+        let mut game = Game::new(2, 5).unwrap();
+        game.points_row.pop();
+        assert_eq!(
+            Game::from_save_str(&game.to_save_string().unwrap()),
+            Err(GameErrors::InvalidSaveFile(
+                "the points row holds 4 cards instead of 5".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use super::Game;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A player's final result, as reported by [`Game::standings`].
+pub struct Standing {
+    /// Index of the player in [`Game::get_players`].
+    pub player: usize,
+    /// Final position, starting at 1 for the winner.
+    pub rank: usize,
+    /// Number of points cards claimed.
+    pub points_cards: usize,
+    /// Points from the claimed points cards.
+    pub card_points: u32,
+    /// Points from gold and silver coins.
+    pub coin_points: u32,
+    /// Points from non-turmeric cubes left in the caravan, 1 each.
+    pub spice_points: u32,
+    /// Sum of all points.
+    pub total: u32,
+}
+
+impl Game {
+    /// Get the players' scores, from first to last.
+    ///
+    /// Players with the same total are ranked by turn order, the later player winning the tie.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{cards::{action::PURCHASABLE_ACTION_CARDS, points::POINTS_CARDS}, game::Game};
+    /// let game = Game::with_decks(3, POINTS_CARDS.to_vec(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+    /// let standings = game.standings();
+    /// // Nobody has scored yet, so the last player wins the tie.
+    /// assert_eq!(standings[0].player, 2);
+    /// assert_eq!(standings[0].total, 0);
+    /// ```
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<_> = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(idx, player)| {
+                let spices = player.get_caravan().get_spice_amount();
+                Standing {
+                    player: idx,
+                    rank: 0,
+                    points_cards: player.get_score_pile().len(),
+                    card_points: player
+                        .get_score_pile()
+                        .iter()
+                        .map(|card| card.points as u32)
+                        .sum(),
+                    coin_points: player
+                        .get_coins()
+                        .iter()
+                        .map(|coin| coin.points() as u32)
+                        .sum(),
+                    spice_points: (spices.total() - spices.turmeric as usize) as u32,
+                    total: player.score(),
+                }
+            })
+            .collect();
+
+        standings.sort_by(|a, b| b.total.cmp(&a.total).then(b.player.cmp(&a.player)));
+        for (idx, standing) in standings.iter_mut().enumerate() {
+            standing.rank = idx + 1;
+        }
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::action::PURCHASABLE_ACTION_CARDS;
+    use crate::errors::GameErrors;
+    use crate::game::Game;
+    use crate::player::PlayerAction;
+    use crate::points_card;
+
+    #[test]
+    fn test_game_ends_after_round() {
+        let points_deck = vec![points_card!(1, [0, 0, 0, 0]); 20];
+        let mut game =
+            Game::with_decks(2, points_deck.clone(), PURCHASABLE_ACTION_CARDS.to_vec()).unwrap();
+
+        for _ in 0..4 {
+            game.play(PlayerAction::Score(points_deck[0])).unwrap();
+            game.play(PlayerAction::Score(points_deck[0])).unwrap();
+        }
+        game.play(PlayerAction::Score(points_deck[0])).unwrap();
+        assert!(game.is_end_triggered());
+        assert!(!game.is_over());
+
+        game.play(PlayerAction::Score(points_deck[0])).unwrap();
+        assert!(game.is_over());
+        assert_eq!(
+            game.play(PlayerAction::Score(points_deck[0])),
+            Err(GameErrors::GameOver)
+        );
+
+        // Both players took two gold and two silver coins, so the later player wins the tie.
+        let standings = game.standings();
+        assert_eq!(standings[0].player, 1);
+        assert_eq!(standings[0].rank, 1);
+        assert_eq!(standings[0].points_cards, 5);
+        assert_eq!(standings[0].card_points, 5);
+        assert_eq!(standings[0].coin_points, 8);
+        assert_eq!(standings[0].total, 13);
+        assert_eq!(standings[1].player, 0);
+        assert_eq!(standings[1].total, 13);
+    }
+}
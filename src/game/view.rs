@@ -0,0 +1,164 @@
+use super::{CoinSupply, Game, MerchantSlot};
+use crate::cards::action::{PURCHASABLE_ACTION_CARDS, STARTING_ACTION_CARDS};
+use crate::cards::points::POINTS_CARDS;
+use crate::cards::PointsCard;
+use crate::player::{Player, PlayerAction};
+use crate::rng::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What the player to move can observe of a game: everything except the order of the cards left in the decks.
+pub struct GameView {
+    /// Index of the player to move in `players`.
+    pub seat: usize,
+    /// Face-up points cards.
+    pub points_row: Vec<PointsCard>,
+    /// Face-up action cards, with the spices left on them.
+    pub merchant_row: Vec<MerchantSlot>,
+    /// Coins left above the points row.
+    pub coins: CoinSupply,
+    /// Number of cards left in the points deck.
+    pub points_deck_len: usize,
+    /// Number of cards left in the merchant deck.
+    pub merchant_deck_len: usize,
+    /// All players, in turn order.
+    pub players: Vec<Player>,
+    /// Number of points cards that triggers the end of the game.
+    pub end_threshold: usize,
+    /// Whether the game is in its last round.
+    pub end_triggered: bool,
+    /// Every action played so far.
+    pub history: Vec<PlayerAction>,
+    /// The legal actions of the player to move, see [`Game::legal_actions`].
+    pub legal_actions: Vec<PlayerAction>,
+}
+
+impl GameView {
+    /// Get the player to move.
+    pub fn player(&self) -> &Player {
+        &self.players[self.seat]
+    }
+
+    /// Samples a full game consistent with this view, dealing the cards not yet seen into the decks in a random order.
+    ///
+    /// The cards not yet seen are those of the full decks that are in no row, hand, discard pile or score pile. Bots
+    /// can search the sampled games as if they knew the order of the decks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{game::Game, rng::Rng};
+    /// let game = Game::new(2, 4).unwrap();
+    /// let sampled = game.view().determinize(&mut Rng::new(1));
+    /// assert_eq!(sampled.view(), game.view());
+    /// assert_ne!(sampled, game);
+    /// ```
+    pub fn determinize(&self, rng: &mut Rng) -> Game {
+        let mut points_deck = POINTS_CARDS.to_vec();
+        let seen_points = self
+            .players
+            .iter()
+            .flat_map(|player| player.get_score_pile())
+            .chain(&self.points_row);
+        for card in seen_points {
+            if let Some(idx) = points_deck.iter().position(|x| x == card) {
+                points_deck.swap_remove(idx);
+            }
+        }
+
+        let mut merchant_deck = PURCHASABLE_ACTION_CARDS.to_vec();
+        for player in &self.players {
+            let mut cards: Vec<_> = player
+                .get_hand()
+                .iter()
+                .chain(player.get_discard_pile())
+                .collect();
+            for card in &STARTING_ACTION_CARDS {
+                if let Some(idx) = cards.iter().position(|x| *x == card) {
+                    cards.swap_remove(idx);
+                }
+            }
+            for card in cards {
+                if let Some(idx) = merchant_deck.iter().position(|x| x == card) {
+                    merchant_deck.swap_remove(idx);
+                }
+            }
+        }
+        for slot in &self.merchant_row {
+            if let Some(idx) = merchant_deck.iter().position(|x| *x == slot.card) {
+                merchant_deck.swap_remove(idx);
+            }
+        }
+
+        // Sort before shuffling, so the result only depends on the seed and the cards.
+        points_deck.sort_by_key(ToString::to_string);
+        merchant_deck.sort_by_key(ToString::to_string);
+        rng.shuffle(&mut points_deck);
+        rng.shuffle(&mut merchant_deck);
+        points_deck.truncate(self.points_deck_len);
+        merchant_deck.truncate(self.merchant_deck_len);
+
+        Game {
+            seed: None,
+            points_deck,
+            points_row: self.points_row.clone(),
+            merchant_deck,
+            merchant_row: self.merchant_row.clone(),
+            coins: self.coins,
+            players: self.players.clone(),
+            current_player: self.seat,
+            end_triggered: self.end_triggered,
+            history: self.history.clone(),
+        }
+    }
+}
+
+impl Game {
+    /// Get what the player to move can observe of the game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::Game;
+    /// let game = Game::new(3, 11).unwrap();
+    /// let view = game.view();
+    /// assert_eq!(view.seat, 0);
+    /// assert_eq!(view.points_deck_len, 31);
+    /// assert_eq!(view.legal_actions, game.legal_actions());
+    /// ```
+    pub fn view(&self) -> GameView {
+        GameView {
+            seat: self.current_player,
+            points_row: self.points_row.clone(),
+            merchant_row: self.merchant_row.clone(),
+            coins: self.coins,
+            points_deck_len: self.points_deck.len(),
+            merchant_deck_len: self.merchant_deck.len(),
+            players: self.players.clone(),
+            end_threshold: self.end_threshold(),
+            end_triggered: self.end_triggered,
+            history: self.history.clone(),
+            legal_actions: self.legal_actions(),
+        }
+    }
+
+    /// Get what a given player observes of the game: the same as [`Game::view`], without the legal actions unless the
+    /// player is to move.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::game::Game;
+    /// let game = Game::new(2, 11).unwrap();
+    /// assert_eq!(game.view_for(0), game.view());
+    /// assert_eq!(game.view_for(1).seat, 0);
+    /// assert!(game.view_for(1).legal_actions.is_empty());
+    /// ```
+    pub fn view_for(&self, player: usize) -> GameView {
+        let mut view = self.view();
+        if player != view.seat {
+            view.legal_actions.clear();
+        }
+        view
+    }
+}
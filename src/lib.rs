@@ -1,13 +1,24 @@
+#[cfg(feature = "server")]
+pub mod api;
+pub mod bots;
 pub mod cards;
 pub mod errors;
+pub mod game;
 pub mod macros;
+#[cfg(feature = "serde")]
+pub mod net;
 pub mod player;
+pub mod ratings;
+pub mod rng;
+pub mod solver;
 pub mod spice;
+pub mod tournament;
 
 #[cfg(test)]
 mod tests {
     use crate::errors::GameErrors;
     use crate::spice::SpiceCube;
+    use crate::spice_amount;
 
     #[test]
     fn upgrade_turmeric() {
@@ -36,4 +47,30 @@ mod tests {
         let upgrade_result = cube.upgrade(0);
         assert!(upgrade_result.is_err_and(|x| x == GameErrors::CannotUpgradeToSelf));
     }
+
+    #[test]
+    fn upgrade_options_split_budget() {
+        let spice_amount = spice_amount!(1, 1, 0, 0);
+        let options = spice_amount.upgrade_options(2);
+
+        let results: Vec<_> = options.iter().map(|(_, result)| *result).collect();
+        assert_eq!(
+            results,
+            vec![
+                spice_amount!(0, 2, 0, 0),
+                spice_amount!(0, 1, 1, 0),
+                spice_amount!(1, 0, 1, 0),
+                spice_amount!(1, 0, 0, 1),
+            ]
+        );
+        for (plan, result) in options {
+            assert_eq!(spice_amount.apply_upgrades(&plan, 2), Ok(result));
+        }
+    }
+
+    #[test]
+    fn upgrade_options_cinnamon_only() {
+        let spice_amount = spice_amount!(0, 0, 0, 3);
+        assert!(spice_amount.upgrade_options(3).is_empty());
+    }
 }
@@ -66,7 +66,7 @@ macro_rules! points_card {
 }
 #[cfg(test)]
 mod tests {
-    use crate::{cards::PointsCard, points_card, spice::SpiceAmount, spice_amount};
+    use crate::{cards::PointsCard, spice::SpiceAmount};
 
     #[test]
     fn test_spice_amount_macro() {
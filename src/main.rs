@@ -1,21 +1,382 @@
-use libcsr::cards::points::POINTS_CARDS;
-use libcsr::{errors::GameErrors, spice::SpiceCube};
+#[cfg(feature = "server")]
+use libcsr::api::{ApiServer, DEFAULT_API_PORT};
+use libcsr::bots::external::{ExternalBot, DEFAULT_TIME_LIMIT};
+use libcsr::bots::greedy::GreedyBot;
+use libcsr::bots::mcts::{MctsBot, MctsConfig};
+use libcsr::bots::{random::RandomBot, Bot};
+use libcsr::errors::GameErrors;
+use libcsr::game::{record::GameRecord, Game};
+use libcsr::net::{Client, Server, DEFAULT_PORT};
+use libcsr::ratings::RatingLedger;
+use libcsr::tournament::{self, BotFactory, Entrant, Forfeit, TournamentConfig};
+use std::env;
+use std::fs;
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod terminal;
+#[cfg(feature = "tui")]
+mod tui;
+
+const USAGE: &str = "Usage:
+    csr                                   Play a two player hot-seat game, like \"csr play 2\".
+    csr play <seats> [seed]               Play a hot-seat game in the terminal. <seats> is the number of
+                                          players, or one letter per seat: h (human), r (random bot),
+                                          g (greedy bot) or m (tree search bot). Seats can also be separated
+                                          by commas to name the humans, e.g. \"ann,bo,g\", or to run external
+                                          bots with exec:<command>, e.g. \"ann,exec:python3 bot.py\".
+    csr tui <players> [seed]              Play a hot-seat game in a full-screen interface (needs the \"tui\" feature).
+    csr new <save file> <players> <seed>  Set up a new game and save it.
+    csr resume <save file>                Continue a saved game with the same players and bots, saving after
+                                          every move.
+    csr record <save file>                Print the record of a saved game.
+    csr replay <record file>              Replay a game record, checking every move.
+    csr tournament <bots> [games] [seed]  Play bots against each other, given as for \"play\" (r, g, m or
+                                          exec:<command>), and report their statistics. 1000 games by default,
+                                          rounded up so that every bot plays every seat equally often.
+    csr ratings [name]                    Show the rating leaderboard, or the rating history of a player.
+    csr serve <players> [address] [seed]  Host a game for players joining over the network, listening on
+                                          0.0.0.0:7373 by default.
+    csr join <address> [name]             Join a hosted game, e.g. \"csr join 192.168.1.20 ann\". The port is
+                                          7373 unless given, as in \"192.168.1.20:7400\".
+    csr api [address]                     Serve the JSON API for web clients, on 0.0.0.0:8080 by default
+                                          (needs the \"server\" feature).
+
+When the CSR_RATINGS environment variable names a ratings file, completed games update the ratings stored in
+it, and \"ratings\" reads them. Unnamed humans are rated as \"Player <seat>\".
+
+External bots talk over their standard input and output (see the bots::external module documentation), and
+forfeit the game if they crash, break the protocol or take longer than the number of milliseconds in the
+CSR_BOT_TIME environment variable to move, 5000 by default.";
+
+/// Seats of the game played by `csr` without arguments.
+const DEFAULT_SEATS: &str = "2";
 
 fn main() -> Result<(), GameErrors> {
-    println!("Initializing the game.");
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    let mut sample_cube = SpiceCube::Saffron;
-    let upgrade_steps: u8 = 2;
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => play(DEFAULT_SEATS, clock_seed()),
+        ["new", path, players, seed] => {
+            let (Ok(players), Ok(seed)) = (players.parse(), seed.parse()) else {
+                usage();
+            };
+            let game = Game::new(players, seed)?;
+            game.save(path)?;
+            println!("Saved a new {players} player game (seed {seed}) to {path}.");
+            Ok(())
+        }
+        ["play", seats, ref seed @ ..] if seed.len() <= 1 => {
+            let Ok(seed) = seed.first().map_or(Ok(clock_seed()), |seed| seed.parse()) else {
+                usage();
+            };
+            play(seats, seed)
+        }
+        #[cfg(feature = "tui")]
+        ["tui", players, ref seed @ ..] if seed.len() <= 1 => {
+            let (Ok(players), Ok(seed)) = (
+                players.parse(),
+                seed.first().map_or(Ok(clock_seed()), |seed| seed.parse()),
+            ) else {
+                usage();
+            };
+            match tui::run(Game::new(players, seed)?) {
+                Ok(game) => rate(&game, &seat_names(players), None),
+                Err(error) => {
+                    eprintln!("Terminal error: {error}");
+                    process::exit(1);
+                }
+            }
+        }
+        ["resume", path] => {
+            let (game, mut seats) = Game::load_with_seats(path)?;
+            if seats.is_empty() {
+                seats = vec!["h".to_string(); game.get_players().len()];
+            }
+            let Some((names, bots)) = parse_seats(&seats, clock_seed()) else {
+                return Err(GameErrors::InvalidSaveFile(format!(
+                    "cannot seat {seats:?}"
+                )));
+            };
+            let (game, forfeit) = terminal::play(game, bots, &seats, Some(path))?;
+            rate(&game, &names, forfeit.as_ref())
+        }
+        ["record", path] => {
+            let game = Game::load(path)?;
+            print!("{}", GameRecord::from_game(&game)?);
+            Ok(())
+        }
+        ["replay", path] => {
+            let record: GameRecord = fs::read_to_string(path)
+                .map_err(|error| GameErrors::SaveFileIo(error.to_string()))?
+                .parse()?;
+            let game = record.replay()?;
+            println!("Replayed {} moves.", record.actions.len());
+            terminal::print_game(&game);
+            Ok(())
+        }
+        ["tournament", bots, ref options @ ..] if options.len() <= 2 => {
+            let config = TournamentConfig::default();
+            let (Ok(games), Ok(seed)) = (
+                options
+                    .first()
+                    .map_or(Ok(config.games), |games| games.parse()),
+                options.get(1).map_or(Ok(clock_seed()), |seed| seed.parse()),
+            ) else {
+                usage();
+            };
+            let Some(entrants) = parse_entrants(bots) else {
+                usage();
+            };
+            println!("Seed {seed}");
+            let config = TournamentConfig {
+                games,
+                seed,
+                ..config
+            };
+            let report = tournament::run(&entrants, &config)?;
+            print!("{report}");
+
+            let Some(path) = ratings_path() else {
+                return Ok(());
+            };
+            let mut ledger = RatingLedger::load(&path)?;
+            for game in report
+                .games
+                .iter()
+                .filter(|game| game.finished || game.forfeit.is_some())
+            {
+                let ranking: Vec<_> = game
+                    .ranking()
+                    .into_iter()
+                    .map(|seat| entrants[game.seats[seat]].get_name().to_string())
+                    .collect();
+                ledger.record(Some(game.seed), &ranking)?;
+            }
+            ledger.save(path)
+        }
+        ["serve", players, ref options @ ..] if options.len() <= 2 => {
+            let (Ok(players), Ok(seed)) = (
+                players.parse(),
+                options.get(1).map_or(Ok(clock_seed()), |seed| seed.parse()),
+            ) else {
+                usage();
+            };
+            let address = options
+                .first()
+                .map_or(format!("0.0.0.0:{DEFAULT_PORT}"), |address| {
+                    with_port(address)
+                });
+            let mut server = Server::bind(address, Game::new(players, seed)?)?;
+            println!("Seed {seed}");
+            println!("Waiting for {players} players on {}.", server.local_addr()?);
+            while !server.is_full() {
+                let (seat, name) = server.accept()?;
+                println!("Player {} joined: {name}", seat + 1);
+            }
+            let names = unique_names(server.names());
+            let game = server.run()?;
+            terminal::print_game(&game);
+            rate(&game, &names, None)
+        }
+        ["join", address, ref name @ ..] if name.len() <= 1 => {
+            let client = Client::connect(with_port(address), name.first().unwrap_or(&""))?;
+            terminal::join(client)
+        }
+        #[cfg(feature = "server")]
+        ["api", ref address @ ..] if address.len() <= 1 => {
+            let address = address
+                .first()
+                .map_or(format!("0.0.0.0:{DEFAULT_API_PORT}"), |address| {
+                    address.to_string()
+                });
+            let server = ApiServer::bind(address)?;
+            println!("Serving the API on http://{}", server.local_addr()?);
+            server.run();
+            Ok(())
+        }
+        ["ratings", ref name @ ..] if name.len() <= 1 => {
+            let Some(path) = ratings_path() else {
+                eprintln!("No ratings kept: set CSR_RATINGS to the ratings file.");
+                process::exit(1);
+            };
+            let ledger = RatingLedger::load(path)?;
+            match name.first() {
+                Some(name) => terminal::print_rating_history(&ledger, name),
+                None => terminal::print_leaderboard(&ledger),
+            }
+            Ok(())
+        }
+        _ => usage(),
+    }
+}
+
+/// Plays a hot-seat game in the terminal, see [`USAGE`].
+fn play(seats: &str, seed: u64) -> Result<(), GameErrors> {
+    let seats = seat_list(seats);
+    let Some((names, bots)) = parse_seats(&seats, seed) else {
+        usage();
+    };
+    let (game, forfeit) = terminal::play(Game::new(bots.len(), seed)?, bots, &seats, None)?;
+    rate(&game, &names, forfeit.as_ref())
+}
 
-    println!("I have a {sample_cube:?}");
+/// The players' names for the ratings, and the bot playing each seat, `None` for humans.
+type Seats = (Vec<String>, Vec<Option<Box<dyn Bot>>>);
 
-    if let Ok(upgraded_cube) = sample_cube.upgrade(upgrade_steps) {
-        sample_cube = upgraded_cube;
-        println!("Upgraded cube {upgrade_steps} step(s) to {sample_cube:?}.");
-    } else {
-        println!("Failed to upgrade cube {sample_cube:?} by {upgrade_steps} step(s).");
+/// Splits the seats of a new game, see [`USAGE`], into one seat each: "h" for an unnamed human, a human's name, or a
+/// bot. Saves record them so that resuming a game seats the same players.
+fn seat_list(seats: &str) -> Vec<String> {
+    if let Ok(players) = seats.parse::<usize>() {
+        return vec!["h".to_string(); players];
     }
+    match seats.contains(',') {
+        true => seats
+            .split(',')
+            .map(|seat| seat.trim().to_string())
+            .collect(),
+        false => seats.chars().map(String::from).collect(),
+    }
+}
+
+/// Seats the players given by [`seat_list`]. Bots are seeded from `seed` and their seat.
+fn parse_seats(seats: &[String], seed: u64) -> Option<Seats> {
+    let mut names = Vec::new();
+    let mut bots = Vec::new();
+    for (idx, seat) in seats.iter().enumerate() {
+        match bot_factory(seat) {
+            Some(factory) => {
+                let bot = factory(seed.wrapping_add(idx as u64));
+                names.push(bot.name());
+                bots.push(Some(bot));
+            }
+            None if seat.is_empty() => return None,
+            None if seat == "h" => {
+                names.push(format!("Player {}", idx + 1));
+                bots.push(None);
+            }
+            None => {
+                names.push(seat.clone());
+                bots.push(None);
+            }
+        }
+    }
+    Some((unique_names(names), bots))
+}
+
+/// Get the names of unnamed players, by seat.
+#[cfg(feature = "tui")]
+fn seat_names(players: usize) -> Vec<String> {
+    (1..=players).map(|seat| format!("Player {seat}")).collect()
+}
+
+/// Numbers repeated names, such as two bots of the same kind, so that each player is rated separately.
+fn unique_names(names: Vec<String>) -> Vec<String> {
+    names
+        .iter()
+        .enumerate()
+        .map(
+            |(idx, name)| match names[..idx].iter().filter(|x| *x == name).count() {
+                0 => name.clone(),
+                repeats => format!("{name} {}", repeats + 1),
+            },
+        )
+        .collect()
+}
+
+/// Parses the bots of a tournament, one letter each or separated by commas, naming them after their bot.
+fn parse_entrants(bots: &str) -> Option<Vec<Entrant>> {
+    let factories = match bots.contains(',') {
+        true => bots.split(',').map(|bot| bot_factory(bot.trim())).collect(),
+        false => bots
+            .chars()
+            .map(|letter| bot_factory(&letter.to_string()))
+            .collect::<Option<Vec<_>>>(),
+    }?;
+    let names = unique_names(factories.iter().map(|factory| factory(0).name()).collect());
+    Some(
+        names
+            .into_iter()
+            .zip(factories)
+            .map(|(name, factory)| Entrant::new(name, factory))
+            .collect(),
+    )
+}
+
+/// Get the function creating the bot of a seat from a seed, see [`USAGE`], or `None` if the seat is not a bot.
+fn bot_factory(seat: &str) -> Option<BotFactory> {
+    if let Some(command) = seat.strip_prefix("exec:") {
+        let command: Vec<_> = command.split_whitespace().map(String::from).collect();
+        command.first()?;
+        let time_limit = bot_time_limit();
+        return Some(Box::new(move |_| {
+            let args: Vec<_> = command[1..].iter().map(String::as_str).collect();
+            Box::new(ExternalBot::new(&command[0], &args, time_limit))
+        }));
+    }
+    match seat {
+        "g" => Some(Box::new(|_| Box::new(GreedyBot::default()))),
+        "m" => Some(Box::new(|seed| {
+            Box::new(MctsBot::new(MctsConfig {
+                seed,
+                ..MctsConfig::default()
+            }))
+        })),
+        "r" => Some(Box::new(|seed| Box::new(RandomBot::new(seed)))),
+        _ => None,
+    }
+}
+
+/// Get the time external bots have to move, see [`USAGE`].
+fn bot_time_limit() -> Duration {
+    env::var("CSR_BOT_TIME")
+        .ok()
+        .and_then(|millis| millis.parse().ok())
+        .map_or(DEFAULT_TIME_LIMIT, Duration::from_millis)
+}
+
+/// Adds the default port to a network address without one.
+fn with_port(address: &str) -> String {
+    match address.contains(':') {
+        true => address.to_string(),
+        false => format!("{address}:{DEFAULT_PORT}"),
+    }
+}
+
+/// Get the path of the ratings file, see [`USAGE`].
+fn ratings_path() -> Option<String> {
+    env::var("CSR_RATINGS").ok().filter(|path| !path.is_empty())
+}
+
+/// Records a game in the ratings file, with its players' names by seat, if it is over or was forfeited.
+fn rate(game: &Game, names: &[String], forfeit: Option<&Forfeit>) -> Result<(), GameErrors> {
+    let Some(path) = ratings_path() else {
+        return Ok(());
+    };
+    let mut ledger = RatingLedger::load(&path)?;
+    let rated = match forfeit {
+        Some(forfeit) => ledger.record_forfeit(game, names, forfeit.seat)?,
+        None if game.is_over() => ledger.record_game(game, names)?,
+        None => return Ok(()),
+    };
+    println!("Ratings:");
+    for change in &rated.changes {
+        println!(
+            "  {}: {:.0} -> {:.0}",
+            change.name, change.before, change.after
+        );
+    }
+    ledger.save(path)
+}
+
+/// Picks a seed for games started without one. The seed is shown with the game, so it can still be replayed.
+fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
 
-    println!("Loading {} point cards.", POINTS_CARDS.len());
-    Ok(())
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2);
 }
@@ -0,0 +1,502 @@
+//! Networked games: a [`Server`] hosts a game for players connecting over TCP, each with a [`Client`].
+//!
+//! Messages are JSON objects, one per line, tagged by their `type`. A client joins with a
+//! [`ClientMessage::Join`], and is welcomed with its seat once the server accepts it. When every seat is taken, the
+//! server sends each player their own view of the game after every move (see [`Game::view_for`]), so the order of
+//! the decks never leaves the server. Only the player to move may play, and their actions are checked by
+//! [`Game::play`] as in a local game.
+
+use crate::errors::GameErrors;
+use crate::game::{scoring::Standing, view::GameView, Game};
+use crate::player::PlayerAction;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Port a server listens on, unless given another one.
+pub const DEFAULT_PORT: u16 = 7373;
+
+/// Time a new connection has to send its [`ClientMessage::Join`] before it is dropped.
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+/// A message from a client to the server.
+pub enum ClientMessage {
+    /// Asks for a seat, under a name shown to the other players. A blank name is replaced by `Player <seat>`.
+    Join { name: String },
+    /// Plays an action for the player to move.
+    Play { action: PlayerAction },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+/// A message from the server to a client.
+pub enum ServerMessage {
+    /// Accepts a client, with its seat, starting at 0, and the number of players.
+    Welcome { seat: usize, players: usize },
+    /// Lists the names of the players who have joined, by seat, whenever one joins.
+    Lobby { names: Vec<String> },
+    /// Gives the game as the client's player observes it, after the action that led to it if there is one.
+    State {
+        view: GameView,
+        last: Option<(usize, PlayerAction)>,
+    },
+    /// Refuses the client's last message, such as an illegal action, with the reason.
+    Rejected { reason: String },
+    /// Ends the game, with the final standings.
+    GameOver { standings: Vec<Standing> },
+    /// Stops the game because a player disconnected.
+    Left { name: String },
+}
+
+/// Writes a message as a line of JSON.
+fn send<T: Serialize>(stream: &mut TcpStream, message: &T) -> Result<(), GameErrors> {
+    let line = serde_json::to_string(message)
+        .map_err(|error| GameErrors::InvalidMessage(error.to_string()))?;
+    writeln!(stream, "{line}")
+        .and_then(|()| stream.flush())
+        .map_err(|error| GameErrors::NetworkIo(error.to_string()))
+}
+
+/// Reads a message from a line of JSON, or `None` at the end of the stream.
+fn receive<T: for<'de> Deserialize<'de>>(
+    reader: &mut BufReader<TcpStream>,
+) -> Result<Option<T>, GameErrors> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Ok(None),
+        Ok(_) => serde_json::from_str(&line)
+            .map(Some)
+            .map_err(|error| GameErrors::InvalidMessage(error.to_string())),
+        Err(error) => Err(GameErrors::NetworkIo(error.to_string())),
+    }
+}
+
+#[derive(Debug)]
+/// A connected player.
+struct Seat {
+    name: String,
+    stream: TcpStream,
+    /// Reads the player's messages, until it is handed to a thread when the game starts.
+    reader: Option<BufReader<TcpStream>>,
+}
+
+/// What a connection's reader thread reports to the server.
+enum Event {
+    Message(usize, ClientMessage),
+    Invalid(usize, GameErrors),
+    Left(usize),
+}
+
+#[derive(Debug)]
+/// Hosts a game for players connecting over TCP, one connection per seat.
+pub struct Server {
+    listener: TcpListener,
+    game: Game,
+    seats: Vec<Seat>,
+}
+
+impl Server {
+    /// Listens for players on an address, such as `0.0.0.0:7373` for every network interface or `127.0.0.1:0` for a
+    /// free port on this machine only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NetworkIo` if the address cannot be listened on.
+    pub fn bind(address: impl ToSocketAddrs, game: Game) -> Result<Self, GameErrors> {
+        let listener =
+            TcpListener::bind(address).map_err(|error| GameErrors::NetworkIo(error.to_string()))?;
+        Ok(Self {
+            listener,
+            game,
+            seats: Vec::new(),
+        })
+    }
+
+    /// Get the address the server listens on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NetworkIo` if the address cannot be read.
+    pub fn local_addr(&self) -> Result<SocketAddr, GameErrors> {
+        self.listener
+            .local_addr()
+            .map_err(|error| GameErrors::NetworkIo(error.to_string()))
+    }
+
+    /// Get the names of the players who have joined, by seat.
+    pub fn names(&self) -> Vec<String> {
+        self.seats.iter().map(|seat| seat.name.clone()).collect()
+    }
+
+    /// Whether every seat is taken.
+    pub fn is_full(&self) -> bool {
+        self.seats.len() == self.game.get_players().len()
+    }
+
+    /// Waits for the next player to join, and returns their seat and name.
+    ///
+    /// Connections that do not start with a [`ClientMessage::Join`] are dropped, and the server keeps waiting.
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::InvalidNumberOfPlayers` if every seat is already taken.
+    /// * `GameErrors::NetworkIo` if the server cannot accept connections.
+    pub fn accept(&mut self) -> Result<(usize, String), GameErrors> {
+        if self.is_full() {
+            return Err(GameErrors::InvalidNumberOfPlayers(self.seats.len() + 1));
+        }
+        let seat = self.seats.len();
+        loop {
+            let (mut stream, _) = self
+                .listener
+                .accept()
+                .map_err(|error| GameErrors::NetworkIo(error.to_string()))?;
+            let Ok(mut reader) = stream.try_clone().map(BufReader::new) else {
+                continue;
+            };
+            stream.set_read_timeout(Some(JOIN_TIMEOUT)).ok();
+            let name = match receive(&mut reader) {
+                Ok(Some(ClientMessage::Join { name })) if name.trim().is_empty() => {
+                    format!("Player {}", seat + 1)
+                }
+                Ok(Some(ClientMessage::Join { name })) => name.trim().to_string(),
+                _ => {
+                    let reason = "Expected a join message".to_string();
+                    send(&mut stream, &ServerMessage::Rejected { reason }).ok();
+                    continue;
+                }
+            };
+            stream.set_read_timeout(None).ok();
+            // Moves are small messages that should not wait to be batched.
+            stream.set_nodelay(true).ok();
+
+            let players = self.game.get_players().len();
+            if send(&mut stream, &ServerMessage::Welcome { seat, players }).is_err() {
+                continue;
+            }
+            self.seats.push(Seat {
+                name: name.clone(),
+                stream,
+                reader: Some(reader),
+            });
+            let names = self.names();
+            self.broadcast(&ServerMessage::Lobby { names });
+            return Ok((seat, name));
+        }
+    }
+
+    /// Sends a message to every player. Players who cannot be reached are found out by their reader thread.
+    fn broadcast(&mut self, message: &ServerMessage) {
+        for seat in &mut self.seats {
+            send(&mut seat.stream, message).ok();
+        }
+    }
+
+    /// Sends each player their view of the game, after the last action played if there is one.
+    fn broadcast_state(&mut self, last: Option<(usize, PlayerAction)>) {
+        for (idx, seat) in self.seats.iter_mut().enumerate() {
+            let message = ServerMessage::State {
+                view: self.game.view_for(idx),
+                last: last.clone(),
+            };
+            send(&mut seat.stream, &message).ok();
+        }
+    }
+
+    /// Plays the game until it is over, first waiting for the seats still free to be taken. Returns the finished
+    /// game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{bots::{random::RandomBot, Bot}, game::Game, net::{Client, Server, ServerMessage}};
+    /// let server = Server::bind("127.0.0.1:0", Game::new(2, 3).unwrap()).unwrap();
+    /// let address = server.local_addr().unwrap();
+    /// let host = std::thread::spawn(move || server.run());
+    ///
+    /// let mut clients = [Client::connect(address, "ann").unwrap(), Client::connect(address, "bo").unwrap()];
+    /// let mut bot = RandomBot::new(1);
+    /// 'game: loop {
+    ///     for client in &mut clients {
+    ///         match client.receive().unwrap() {
+    ///             ServerMessage::State { view, .. } if !view.legal_actions.is_empty() => {
+    ///                 client.play(bot.choose_action(&view).unwrap()).unwrap();
+    ///             }
+    ///             ServerMessage::GameOver { .. } => break 'game,
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    /// assert!(host.join().unwrap().unwrap().is_over());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::PlayerLeft` if a player disconnects before the end of the game.
+    /// * Any of the errors of [`Server::accept`].
+    pub fn run(mut self) -> Result<Game, GameErrors> {
+        while !self.is_full() {
+            self.accept()?;
+        }
+
+        let (sender, events) = mpsc::channel();
+        for (idx, seat) in self.seats.iter_mut().enumerate() {
+            let mut reader = seat.reader.take().ok_or(GameErrors::InternalLogicError)?;
+            let sender = sender.clone();
+            thread::spawn(move || loop {
+                let event = match receive(&mut reader) {
+                    Ok(Some(message)) => Event::Message(idx, message),
+                    Ok(None) | Err(GameErrors::NetworkIo(_)) => {
+                        sender.send(Event::Left(idx)).ok();
+                        break;
+                    }
+                    Err(error) => Event::Invalid(idx, error),
+                };
+                if sender.send(event).is_err() {
+                    break;
+                }
+            });
+        }
+
+        self.broadcast_state(None);
+        while !self.game.is_over() {
+            let (seat, reason) = match events.recv() {
+                Ok(Event::Message(seat, ClientMessage::Play { action }))
+                    if seat == self.game.get_current_player_index() =>
+                {
+                    let played = self
+                        .game
+                        .check_limits(&action)
+                        .and_then(|()| self.game.play(action.clone()));
+                    match played {
+                        Ok(()) => {
+                            self.broadcast_state(Some((seat, action)));
+                            continue;
+                        }
+                        Err(error) => (seat, error.to_string()),
+                    }
+                }
+                Ok(Event::Message(seat, ClientMessage::Play { .. })) => {
+                    (seat, "It is not your turn".to_string())
+                }
+                Ok(Event::Message(seat, ClientMessage::Join { .. })) => {
+                    (seat, "Already joined".to_string())
+                }
+                Ok(Event::Invalid(seat, error)) => (seat, error.to_string()),
+                Ok(Event::Left(seat)) => {
+                    let name = self.seats[seat].name.clone();
+                    self.broadcast(&ServerMessage::Left { name: name.clone() });
+                    return Err(GameErrors::PlayerLeft(name));
+                }
+                Err(_) => return Err(GameErrors::InternalLogicError),
+            };
+            send(
+                &mut self.seats[seat].stream,
+                &ServerMessage::Rejected { reason },
+            )
+            .ok();
+        }
+
+        let standings = self.game.standings();
+        self.broadcast(&ServerMessage::GameOver { standings });
+        Ok(self.game)
+    }
+}
+
+#[derive(Debug)]
+/// A player's connection to a [`Server`].
+pub struct Client {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    seat: usize,
+    players: usize,
+}
+
+impl Client {
+    /// Connects to a server and joins its game under a name, waiting to be given a seat.
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::NetworkIo` if the server cannot be reached, or closes the connection.
+    /// * `GameErrors::InvalidMessage` if the server does not welcome the player.
+    pub fn connect(address: impl ToSocketAddrs, name: &str) -> Result<Self, GameErrors> {
+        let mut stream = TcpStream::connect(address)
+            .map_err(|error| GameErrors::NetworkIo(error.to_string()))?;
+        stream.set_nodelay(true).ok();
+        let reader = stream
+            .try_clone()
+            .map(BufReader::new)
+            .map_err(|error| GameErrors::NetworkIo(error.to_string()))?;
+        let name = name.to_string();
+        send(&mut stream, &ClientMessage::Join { name })?;
+
+        let mut client = Self {
+            stream,
+            reader,
+            seat: 0,
+            players: 0,
+        };
+        match client.receive()? {
+            ServerMessage::Welcome { seat, players } => {
+                client.seat = seat;
+                client.players = players;
+                Ok(client)
+            }
+            message => Err(GameErrors::InvalidMessage(format!(
+                "expected a welcome, got {message:?}"
+            ))),
+        }
+    }
+
+    /// Get the player's seat, starting at 0.
+    pub fn get_seat(&self) -> usize {
+        self.seat
+    }
+
+    /// Get the number of players in the game.
+    pub fn get_players(&self) -> usize {
+        self.players
+    }
+
+    /// Waits for the next message from the server.
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::NetworkIo` if the connection fails or the server closes it.
+    /// * `GameErrors::InvalidMessage` if the server sends something that is not a [`ServerMessage`].
+    pub fn receive(&mut self) -> Result<ServerMessage, GameErrors> {
+        receive(&mut self.reader)?.ok_or(GameErrors::NetworkIo(
+            "the server closed the connection".to_string(),
+        ))
+    }
+
+    /// Sends an action for the player. The server replies with the next state, or rejects the action.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NetworkIo` if the connection fails.
+    pub fn play(&mut self, action: PlayerAction) -> Result<(), GameErrors> {
+        send(&mut self.stream, &ClientMessage::Play { action })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bots::{random::RandomBot, Bot};
+    use crate::errors::GameErrors;
+    use crate::game::{view::GameView, Game};
+    use crate::net::{Client, Server, ServerMessage};
+    use crate::player::PlayerAction;
+    use crate::spice_amount;
+    use std::thread;
+
+    /// Skips lobby messages until the next state.
+    fn next_view(client: &mut Client) -> GameView {
+        loop {
+            match client.receive().unwrap() {
+                ServerMessage::Lobby { .. } => {}
+                ServerMessage::State { view, .. } => return view,
+                message => panic!("expected a state, got {message:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_networked_game() {
+        let server = Server::bind("127.0.0.1:0", Game::new(3, 6).unwrap()).unwrap();
+        let address = server.local_addr().unwrap();
+        let host = thread::spawn(move || server.run());
+        let mut clients: Vec<_> = ["ann", "", "cy"]
+            .iter()
+            .map(|name| Client::connect(address, name).unwrap())
+            .collect();
+        assert_eq!(clients[2].get_seat(), 2);
+        assert_eq!(clients[2].get_players(), 3);
+        match clients[2].receive().unwrap() {
+            ServerMessage::Lobby { names } => assert_eq!(names, ["ann", "Player 2", "cy"]),
+            message => panic!("expected the lobby, got {message:?}"),
+        }
+
+        let mut views: Vec<_> = clients.iter_mut().map(next_view).collect();
+        assert!(views[1].legal_actions.is_empty());
+        clients[1].play(PlayerAction::Rest).unwrap();
+        assert_eq!(
+            clients[1].receive().unwrap(),
+            ServerMessage::Rejected {
+                reason: "It is not your turn".to_string()
+            }
+        );
+        clients[0].play(PlayerAction::Rest).unwrap();
+        assert_eq!(
+            clients[0].receive().unwrap(),
+            ServerMessage::Rejected {
+                reason: GameErrors::EmptyDiscardPile.to_string()
+            }
+        );
+        let payment = spice_amount!(200, 57, 0, 0);
+        let card = views[0].merchant_row[0].card;
+        clients[0]
+            .play(PlayerAction::AcquireCard(card, payment))
+            .unwrap();
+        assert_eq!(
+            clients[0].receive().unwrap(),
+            ServerMessage::Rejected {
+                reason: GameErrors::TooManySpices(5).to_string()
+            }
+        );
+
+        let mut bot = RandomBot::new(6);
+        let mut local = Game::new(3, 6).unwrap();
+        while !local.is_over() {
+            let seat = views[0].seat;
+            assert_eq!(views[seat].legal_actions, local.legal_actions());
+            let action = bot.choose_action(&views[seat]).unwrap();
+            clients[seat].play(action.clone()).unwrap();
+            local.play(action).unwrap();
+            for (idx, client) in clients.iter_mut().enumerate() {
+                views[idx] = match client.receive().unwrap() {
+                    ServerMessage::State { view, .. } => view,
+                    message => panic!("expected a state, got {message:?}"),
+                };
+                assert_eq!(views[idx], local.view_for(idx));
+            }
+        }
+        for client in &mut clients {
+            assert_eq!(
+                client.receive().unwrap(),
+                ServerMessage::GameOver {
+                    standings: local.standings()
+                }
+            );
+        }
+        assert_eq!(host.join().unwrap(), Ok(local));
+    }
+
+    #[test]
+    fn test_player_leaves() {
+        let server = Server::bind("127.0.0.1:0", Game::new(2, 6).unwrap()).unwrap();
+        let address = server.local_addr().unwrap();
+        let host = thread::spawn(move || server.run());
+        let mut ann = Client::connect(address, "ann").unwrap();
+        let mut bo = Client::connect(address, "bo").unwrap();
+        next_view(&mut ann);
+        next_view(&mut bo);
+
+        drop(bo);
+        assert_eq!(
+            ann.receive().unwrap(),
+            ServerMessage::Left {
+                name: "bo".to_string()
+            }
+        );
+        assert_eq!(
+            host.join().unwrap(),
+            Err(GameErrors::PlayerLeft("bo".to_string()))
+        );
+    }
+}
@@ -1,16 +1,27 @@
-use crate::cards::{ActionCard, PointsCard};
+use crate::cards::action::STARTING_ACTION_CARDS;
+use crate::cards::{ActionCard, CardChoice, PointsCard};
 use crate::errors::GameErrors;
-use crate::spice::{SpiceAmount, SpiceAmountBuilder, SpiceCube};
+use crate::game::Coin;
+use crate::spice::{SpiceAmount, SpiceAmountBuilder, SpiceCube, UpgradePlan};
+use std::fmt;
+use std::str::FromStr;
 
 /// Maximum number of spice cubes a caravan can hold.
 pub const MAX_CARAVAN_SIZE: usize = 10;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SpiceAmount", into = "SpiceAmount")
+)]
 /// This represents a player's caravan, or their inventory.
 ///
-/// A caravan can hold up to [`MAX_CARAVAN_SIZE`] spice cubes.
+/// A caravan can hold up to [`MAX_CARAVAN_SIZE`] spice cubes. Gaining more leaves the extra cubes pending in an
+/// overflow until the player discards down to [`MAX_CARAVAN_SIZE`] with [`Caravan::discard`].
 pub struct Caravan {
     spaces: [Option<SpiceCube>; MAX_CARAVAN_SIZE],
+    overflow: SpiceAmount,
 }
 
 impl Caravan {
@@ -51,39 +62,41 @@ impl Caravan {
     /// assert_eq!(result, Err(GameErrors::MaxSpiceCapacityReached));
     /// ```
     pub fn from_spice_amount(spice_amount: SpiceAmount) -> Result<Self, GameErrors> {
-        // This copies, it does not move-out.
-        let spice_vector: [u8; 4] = spice_amount.into();
-        let [turmeric, saffron, cardamon, cinnamon] = spice_vector.map(|x| x as usize);
-        let total = turmeric + saffron + cardamon + cinnamon;
-
-        if total > MAX_CARAVAN_SIZE {
+        if spice_amount.total() > MAX_CARAVAN_SIZE {
             return Err(GameErrors::MaxSpiceCapacityReached);
         }
 
+        Ok(Self::holding(spice_amount))
+    }
+
+    /// Creates a caravan holding the given spices, placing those beyond [`MAX_CARAVAN_SIZE`] in the overflow.
+    fn holding(spice_amount: SpiceAmount) -> Self {
         let mut spaces = [None; MAX_CARAVAN_SIZE];
-        let mut idx = 0;
-
-        // Use slice patterns to fill spaces
-        let spices = [
-            (turmeric, SpiceCube::Turmeric),
-            (saffron, SpiceCube::Saffron),
-            (cardamon, SpiceCube::Cardamon),
-            (cinnamon, SpiceCube::Cinnamon),
-        ];
-
-        for (count, spice) in spices {
-            for _ in 0..count {
-                spaces[idx] = Some(spice);
-                idx += 1;
+        let mut overflow = SpiceAmount::default();
+
+        for (idx, cube) in spice_amount.cubes().enumerate() {
+            match spaces.get_mut(idx) {
+                Some(space) => *space = Some(cube),
+                None => overflow = overflow.add(&SpiceAmount::from(cube)),
             }
         }
 
-        Ok(Self { spaces })
+        Self { spaces, overflow }
+    }
+
+    /// Get the total number of spices in the caravan, including any overflow.
+    pub fn current_capacity(&self) -> usize {
+        self.spaces.iter().flatten().count() + self.overflow.total()
+    }
+
+    /// Get the number of cubes that must be discarded to get back to [`MAX_CARAVAN_SIZE`].
+    pub fn excess(&self) -> usize {
+        self.overflow.total()
     }
 
-    /// Get the total number of spices in the caravan.
-    pub fn current_capacity(&self) -> u8 {
-        self.spaces.iter().flatten().count() as u8
+    /// Check if the caravan holds more than [`MAX_CARAVAN_SIZE`] cubes and is waiting for a discard.
+    pub fn is_overflowing(&self) -> bool {
+        self.excess() > 0
     }
 
     /// Get the `SpiceAmount` in the caravan.
@@ -123,33 +136,555 @@ impl Caravan {
                     .count() as u8,
             )
             .build()
+            .add(&self.overflow)
+    }
+
+    /// Upgrades cubes in the caravan following an [`UpgradePlan`], spending at most `budget` steps.
+    ///
+    /// The caravan is left untouched if the plan is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, player::Caravan, spice::SpiceCube::{Turmeric, Saffron}};
+    /// let mut caravan = Caravan::from_spice_amount(spice_amount!(3, 1, 0, 0)).unwrap();
+    /// caravan.upgrade(&[(Turmeric, 1), (Saffron, 2)], 3).unwrap();
+    /// assert_eq!(caravan.get_spice_amount(), spice_amount!(2, 1, 0, 1));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any of the errors of [`SpiceAmount::apply_upgrades`].
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, errors::GameErrors, player::Caravan, spice::SpiceCube::Saffron};
+    /// let mut caravan = Caravan::from_spice_amount(spice_amount!(3, 1, 0, 0)).unwrap();
+    /// assert_eq!(caravan.upgrade(&[(Saffron, 3)], 3), Err(GameErrors::CannotUpgradePastCinnamon));
+    /// ```
+    pub fn upgrade(&mut self, upgrades: &[(SpiceCube, u8)], budget: u8) -> Result<(), GameErrors> {
+        let spice_amount = self.get_spice_amount().apply_upgrades(upgrades, budget)?;
+        *self = Self::from_spice_amount(spice_amount)?;
+        Ok(())
+    }
+
+    /// Lists every distinct `SpiceAmount` the caravan can hold after upgrading with at most `budget` steps, together
+    /// with an [`UpgradePlan`] reaching it. See [`SpiceAmount::upgrade_options`].
+    pub fn upgrade_options(&self, budget: u8) -> Vec<(UpgradePlan, SpiceAmount)> {
+        self.get_spice_amount().upgrade_options(budget)
+    }
+
+    /// Adds spices to the caravan, even past [`MAX_CARAVAN_SIZE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, player::Caravan};
+    /// let mut caravan = Caravan::from_spice_amount(spice_amount!(7, 1, 0, 0)).unwrap();
+    /// caravan.add(&spice_amount!(0, 1, 1, 1));
+    /// assert_eq!(caravan.get_spice_amount(), spice_amount!(7, 2, 1, 1));
+    /// assert_eq!(caravan.current_capacity(), 11);
+    /// assert!(caravan.is_overflowing());
+    /// ```
+    pub fn add(&mut self, spice_amount: &SpiceAmount) {
+        *self = Self::holding(self.get_spice_amount().add(spice_amount));
+    }
+
+    /// Discards cubes of the player's choice from an overflowing caravan, bringing it back to [`MAX_CARAVAN_SIZE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, player::Caravan};
+    /// let mut caravan = Caravan::from_spice_amount(spice_amount!(7, 1, 0, 0)).unwrap();
+    /// caravan.add(&spice_amount!(0, 1, 1, 1));
+    /// caravan.discard(&spice_amount!(1, 0, 0, 0)).unwrap();
+    /// assert_eq!(caravan.get_spice_amount(), spice_amount!(6, 2, 1, 1));
+    /// assert!(!caravan.is_overflowing());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::NothingToDiscard` if the caravan is not overflowing, `GameErrors::InvalidDiscard` if the
+    /// number of cubes discarded is not exactly [`Caravan::excess`], or `GameErrors::CannotSubtractSpiceAmount` if the
+    /// caravan does not hold them.
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, errors::GameErrors, player::Caravan};
+    /// let mut caravan = Caravan::from_spice_amount(spice_amount!(7, 1, 0, 0)).unwrap();
+    /// caravan.add(&spice_amount!(0, 1, 1, 1));
+    /// assert_eq!(caravan.discard(&spice_amount!(2, 0, 0, 0)), Err(GameErrors::InvalidDiscard(1)));
+    /// ```
+    pub fn discard(&mut self, spice_amount: &SpiceAmount) -> Result<(), GameErrors> {
+        if !self.is_overflowing() {
+            return Err(GameErrors::NothingToDiscard);
+        }
+        if spice_amount.total() != self.excess() {
+            return Err(GameErrors::InvalidDiscard(self.excess()));
+        }
+
+        let remainder = self.get_spice_amount().subtract(spice_amount)?;
+        *self = Self::holding(remainder);
+        Ok(())
     }
 }
 
-enum PlayerAction {
-    PlayCard(ActionCard),
-    AcquireCard(ActionCard),
+/// Creates a caravan holding the given spices, placing those beyond [`MAX_CARAVAN_SIZE`] in the overflow.
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{spice_amount, player::Caravan};
+/// let caravan = Caravan::from(spice_amount!(9, 2, 0, 0));
+/// assert_eq!(caravan.excess(), 1);
+/// ```
+impl From<SpiceAmount> for Caravan {
+    fn from(spice_amount: SpiceAmount) -> Self {
+        Self::holding(spice_amount)
+    }
+}
+
+/// Get the `SpiceAmount` held by a caravan, see [`Caravan::get_spice_amount`].
+impl From<Caravan> for SpiceAmount {
+    fn from(caravan: Caravan) -> Self {
+        caravan.get_spice_amount()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+/// An action a player can take on their turn.
+pub enum PlayerAction {
+    /// Play an action card from the hand, moving it to the discard pile.
+    PlayCard(ActionCard, CardChoice),
+    /// Acquire an action card, adding it to the hand.
+    ///
+    /// The `SpiceAmount` is the payment placed on the cards skipped in the merchant row, one cube per card. Unlike the
+    /// printed rules, the player does not choose which cube goes on which card, see [`Game::play`](crate::game::Game::play).
+    AcquireCard(ActionCard, SpiceAmount),
+    /// Return all cards from the discard pile to the hand.
     Rest,
+    /// Claim a points card by paying its cost from the caravan.
     Score(PointsCard),
+    /// Discard cubes from an overflowing caravan.
+    Discard(SpiceAmount),
 }
 
 impl PlayerAction {
-    fn play(&self) -> Result<(), GameErrors> {
-        todo!()
+    /// Applies this action to the given player.
+    ///
+    /// The player is left untouched if the action is illegal.
+    ///
+    /// # Examples
+    ///
+    /// Play the starting `Gain` card:
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{ActionCard, CardChoice}, player::{Caravan, Player, PlayerAction}};
+    /// let caravan = Caravan::from_spice_amount(spice_amount!(3, 0, 0, 0)).unwrap();
+    /// let mut player = Player::new(1, caravan);
+    /// let action = PlayerAction::PlayCard(ActionCard::Gain(spice_amount!(2, 0, 0, 0)), CardChoice::Gain);
+    /// action.play(&mut player).unwrap();
+    /// assert_eq!(player.get_caravan().get_spice_amount(), spice_amount!(5, 0, 0, 0));
+    /// assert_eq!(player.get_discard_pile(), &[ActionCard::Gain(spice_amount!(2, 0, 0, 0))]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::DiscardPending` if the player's caravan is overflowing and the action is not a discard.
+    /// * `GameErrors::CardNotInHand` if the card played is not in the player's hand.
+    /// * `GameErrors::EmptyDiscardPile` if the player rests with nothing to pick up.
+    /// * `GameErrors::CannotSubtractSpiceAmount` if the caravan cannot pay for a card.
+    /// * Any of the errors of [`ActionCard::play`] or [`Caravan::discard`].
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{ActionCard, CardChoice}, errors::GameErrors, player::{Caravan, Player, PlayerAction}};
+    /// let caravan = Caravan::from_spice_amount(spice_amount!(3, 0, 0, 0)).unwrap();
+    /// let mut player = Player::new(1, caravan);
+    /// let action = PlayerAction::PlayCard(ActionCard::Upgrade(3), CardChoice::Upgrade(vec![]));
+    /// assert_eq!(action.play(&mut player), Err(GameErrors::CardNotInHand));
+    /// ```
+    pub fn play(&self, player: &mut Player) -> Result<(), GameErrors> {
+        if player.caravan.is_overflowing() && !matches!(self, PlayerAction::Discard(_)) {
+            return Err(GameErrors::DiscardPending(player.caravan.excess()));
+        }
+
+        match self {
+            PlayerAction::PlayCard(card, choice) => {
+                let idx = player
+                    .hand
+                    .iter()
+                    .position(|x| x == card)
+                    .ok_or(GameErrors::CardNotInHand)?;
+                let spice_amount = card.play(&player.caravan.get_spice_amount(), choice)?;
+                player.caravan = Caravan::holding(spice_amount);
+                let card = player.hand.remove(idx);
+                player.discard_pile.push(card);
+            }
+            PlayerAction::AcquireCard(card, payment) => {
+                let remainder = player.caravan.get_spice_amount().subtract(payment)?;
+                player.caravan = Caravan::from_spice_amount(remainder)?;
+                player.hand.push(*card);
+            }
+            PlayerAction::Rest => {
+                if player.discard_pile.is_empty() {
+                    return Err(GameErrors::EmptyDiscardPile);
+                }
+                player.hand.append(&mut player.discard_pile);
+            }
+            PlayerAction::Score(card) => {
+                let (_, remainder) = card.purchase(&player.caravan.get_spice_amount())?;
+                player.caravan = Caravan::from_spice_amount(remainder)?;
+                player.score_pile.push(*card);
+            }
+            PlayerAction::Discard(spice_amount) => player.caravan.discard(spice_amount)?,
+        }
+
+        player.play_history.push(self.clone());
+        Ok(())
+    }
+}
+
+/// Writes an action as a keyword followed by the cards and spices involved, in the notation of [`SpiceAmount`],
+/// [`ActionCard`] and [`PointsCard`]:
+///
+/// * `play YY`, `play YYY>GG x2` (the number of exchanges) or `play U2 Y+1 R+1` (each cube upgraded, with its steps).
+/// * `acquire YYY>GG YR` (the card and the spices placed on the cards it skips, `-` for none).
+/// * `rest`.
+/// * `score 12:RRGG`.
+/// * `discard YY`.
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{spice_amount, cards::{ActionCard, CardChoice}, player::PlayerAction, spice::SpiceCube::Turmeric};
+/// let action = PlayerAction::PlayCard(ActionCard::Upgrade(2), CardChoice::Upgrade(vec![(Turmeric, 2)]));
+/// assert_eq!(action.to_string(), "play U2 Y+2");
+/// assert_eq!("play U2 Y+2".parse(), Ok(action));
+/// assert_eq!("acquire U3 -".parse(), Ok(PlayerAction::AcquireCard(ActionCard::Upgrade(3), spice_amount!(0, 0, 0, 0))));
+/// ```
+impl fmt::Display for PlayerAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayerAction::PlayCard(card, choice) => {
+                write!(f, "play {card}")?;
+                match choice {
+                    CardChoice::Gain => Ok(()),
+                    CardChoice::Exchange(times) => write!(f, " x{times}"),
+                    CardChoice::Upgrade(plan) => plan
+                        .iter()
+                        .try_for_each(|(cube, steps)| write!(f, " {cube}+{steps}")),
+                }
+            }
+            PlayerAction::AcquireCard(card, payment) => write!(f, "acquire {card} {payment}"),
+            PlayerAction::Rest => write!(f, "rest"),
+            PlayerAction::Score(card) => write!(f, "score {card}"),
+            PlayerAction::Discard(spices) => write!(f, "discard {spices}"),
+        }
+    }
+}
+
+/// Parses the notation written by `Display`, with the words separated by any whitespace.
+///
+/// # Errors
+///
+/// Returns `GameErrors::InvalidNotation` for the first word that cannot be parsed.
+///
+/// ```
+/// use libcsr::{errors::GameErrors, player::PlayerAction};
+/// let result = "play YYY>GG x".parse::<PlayerAction>();
+/// assert_eq!(result, Err(GameErrors::InvalidNotation("x".to_string(), 2, "expected the number of exchanges".to_string())));
+/// ```
+impl FromStr for PlayerAction {
+    type Err = GameErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            ["play", card, ref choice @ ..] => Ok(PlayerAction::PlayCard(
+                card.parse()?,
+                parse_card_choice(choice)?,
+            )),
+            ["acquire", card, payment] => {
+                Ok(PlayerAction::AcquireCard(card.parse()?, payment.parse()?))
+            }
+            ["rest"] => Ok(PlayerAction::Rest),
+            ["score", card] => Ok(PlayerAction::Score(card.parse()?)),
+            ["discard", spices] => Ok(PlayerAction::Discard(spices.parse()?)),
+            _ => Err(GameErrors::InvalidNotation(
+                s.to_string(),
+                1,
+                "expected play, acquire, rest, score or discard with their cards and spices"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+/// Parses the words following the card of a `play` action.
+fn parse_card_choice(words: &[&str]) -> Result<CardChoice, GameErrors> {
+    match words {
+        [] => Ok(CardChoice::Gain),
+        [times] if times.starts_with('x') => {
+            times[1..].parse().map(CardChoice::Exchange).map_err(|_| {
+                GameErrors::InvalidNotation(
+                    times.to_string(),
+                    2,
+                    "expected the number of exchanges".to_string(),
+                )
+            })
+        }
+        _ => words
+            .iter()
+            .map(|word| {
+                let invalid = |column: usize, reason: &str| {
+                    GameErrors::InvalidNotation(word.to_string(), column, reason.to_string())
+                };
+                let (cube, steps) = word
+                    .split_once('+')
+                    .ok_or_else(|| invalid(1, "expected an upgrade such as \"Y+1\""))?;
+                let cube = cube.parse::<SpiceCube>()?;
+                let steps = steps
+                    .parse()
+                    .map_err(|_| invalid(3, "expected the number of steps"))?;
+                Ok((cube, steps))
+            })
+            .collect::<Result<_, _>>()
+            .map(CardChoice::Upgrade),
     }
 }
 
-struct Player {
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A player, with their caravan and cards.
+pub struct Player {
     caravan: Caravan,
     player_order: u8,
     hand: Vec<ActionCard>,
     discard_pile: Vec<ActionCard>,
     score_pile: Vec<PointsCard>,
+    coins: Vec<Coin>,
     play_history: Vec<PlayerAction>,
 }
 
 impl Player {
-    fn new() -> Self {
-        todo!()
+    /// Creates a new `Player` holding the [`STARTING_ACTION_CARDS`] and the given caravan.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::action::STARTING_ACTION_CARDS, player::{Caravan, Player}};
+    /// let caravan = Caravan::from_spice_amount(spice_amount!(4, 0, 0, 0)).unwrap();
+    /// let player = Player::new(2, caravan);
+    /// assert_eq!(player.get_player_order(), 2);
+    /// assert_eq!(player.get_hand(), &STARTING_ACTION_CARDS);
+    /// assert!(player.get_discard_pile().is_empty());
+    /// ```
+    pub fn new(player_order: u8, caravan: Caravan) -> Self {
+        Self {
+            caravan,
+            player_order,
+            hand: STARTING_ACTION_CARDS.to_vec(),
+            discard_pile: Vec::new(),
+            score_pile: Vec::new(),
+            coins: Vec::new(),
+            play_history: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the player's caravan.
+    pub fn get_caravan(&self) -> &Caravan {
+        &self.caravan
+    }
+
+    /// Get the player's seat in the turn order, starting at 1.
+    pub fn get_player_order(&self) -> u8 {
+        self.player_order
+    }
+
+    /// Get the action cards in the player's hand.
+    pub fn get_hand(&self) -> &[ActionCard] {
+        &self.hand
+    }
+
+    /// Get the action cards in the player's discard pile.
+    pub fn get_discard_pile(&self) -> &[ActionCard] {
+        &self.discard_pile
+    }
+
+    /// Get the points cards the player has claimed.
+    pub fn get_score_pile(&self) -> &[PointsCard] {
+        &self.score_pile
+    }
+
+    /// Get the coins the player has collected.
+    pub fn get_coins(&self) -> &[Coin] {
+        &self.coins
+    }
+
+    /// Get the player's score: the points from their points cards and coins, plus 1 point per non-turmeric cube in
+    /// their caravan.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::PointsCard, player::{Caravan, Player, PlayerAction}};
+    /// let caravan = Caravan::from_spice_amount(spice_amount!(3, 2, 1, 0)).unwrap();
+    /// let mut player = Player::new(1, caravan);
+    /// PlayerAction::Score(PointsCard { points: 6, cost: spice_amount!(2, 2, 0, 0) }).play(&mut player).unwrap();
+    /// assert_eq!(player.score(), 7);
+    /// ```
+    pub fn score(&self) -> u32 {
+        let cards: u32 = self.score_pile.iter().map(|card| card.points as u32).sum();
+        let coins: u32 = self.coins.iter().map(|coin| coin.points() as u32).sum();
+        let spices = self.caravan.get_spice_amount();
+        cards + coins + (spices.total() - spices.turmeric as usize) as u32
+    }
+
+    /// Get the actions the player has taken so far.
+    pub fn get_play_history(&self) -> &[PlayerAction] {
+        &self.play_history
+    }
+
+    /// Gives the player a coin, for claiming a points card from a coin slot.
+    pub(crate) fn add_coin(&mut self, coin: Coin) {
+        self.coins.push(coin);
+    }
+
+    /// Get a mutable reference to the player's caravan, for effects resolved by the game.
+    pub(crate) fn get_caravan_mut(&mut self) -> &mut Caravan {
+        &mut self.caravan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::{ActionCard, CardChoice, PointsCard};
+    use crate::errors::GameErrors;
+    use crate::player::{Caravan, Player, PlayerAction};
+    use crate::spice::SpiceCube;
+    use crate::{points_card, spice_amount};
+
+    fn player_with(turmeric: u8, saffron: u8, cardamon: u8, cinnamon: u8) -> Player {
+        let caravan =
+            Caravan::from_spice_amount(spice_amount!(turmeric, saffron, cardamon, cinnamon))
+                .unwrap();
+        Player::new(1, caravan)
+    }
+
+    #[test]
+    fn test_play_upgrade_card() {
+        let mut player = player_with(2, 1, 0, 0);
+        let action = PlayerAction::PlayCard(
+            ActionCard::Upgrade(2),
+            CardChoice::Upgrade(vec![(SpiceCube::Saffron, 1), (SpiceCube::Turmeric, 1)]),
+        );
+        action.play(&mut player).unwrap();
+
+        assert_eq!(
+            player.get_caravan().get_spice_amount(),
+            spice_amount!(1, 1, 1, 0)
+        );
+        assert_eq!(
+            player.get_hand(),
+            &[ActionCard::Gain(spice_amount!(2, 0, 0, 0))]
+        );
+        assert_eq!(player.get_discard_pile(), &[ActionCard::Upgrade(2)]);
+        assert_eq!(player.get_play_history(), &[action]);
+    }
+
+    #[test]
+    fn test_play_card_wrong_choice() {
+        let mut player = player_with(3, 0, 0, 0);
+        let before = player.clone();
+        let action = PlayerAction::PlayCard(ActionCard::Upgrade(2), CardChoice::Gain);
+
+        assert_eq!(action.play(&mut player), Err(GameErrors::InvalidCardChoice));
+        assert_eq!(player, before);
+    }
+
+    #[test]
+    fn test_gain_past_capacity() {
+        let mut player = player_with(9, 0, 0, 0);
+        let action = PlayerAction::PlayCard(
+            ActionCard::Gain(spice_amount!(2, 0, 0, 0)),
+            CardChoice::Gain,
+        );
+        action.play(&mut player).unwrap();
+        assert_eq!(player.get_caravan().excess(), 1);
+
+        assert_eq!(
+            PlayerAction::Rest.play(&mut player),
+            Err(GameErrors::DiscardPending(1))
+        );
+        PlayerAction::Discard(spice_amount!(1, 0, 0, 0))
+            .play(&mut player)
+            .unwrap();
+        assert_eq!(
+            player.get_caravan().get_spice_amount(),
+            spice_amount!(10, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_discard_without_overflow() {
+        let mut player = player_with(3, 0, 0, 0);
+        let before = player.clone();
+        assert_eq!(
+            PlayerAction::Discard(spice_amount!(0, 0, 0, 0)).play(&mut player),
+            Err(GameErrors::NothingToDiscard)
+        );
+        assert_eq!(player, before);
+    }
+
+    #[test]
+    fn test_rest() {
+        let mut player = player_with(3, 0, 0, 0);
+        assert_eq!(
+            PlayerAction::Rest.play(&mut player),
+            Err(GameErrors::EmptyDiscardPile)
+        );
+
+        PlayerAction::PlayCard(
+            ActionCard::Gain(spice_amount!(2, 0, 0, 0)),
+            CardChoice::Gain,
+        )
+        .play(&mut player)
+        .unwrap();
+        PlayerAction::Rest.play(&mut player).unwrap();
+
+        assert_eq!(player.get_hand().len(), 2);
+        assert!(player.get_discard_pile().is_empty());
+    }
+
+    #[test]
+    fn test_acquire_and_score() {
+        let mut player = player_with(2, 2, 1, 0);
+        PlayerAction::AcquireCard(ActionCard::Upgrade(3), spice_amount!(0, 0, 1, 0))
+            .play(&mut player)
+            .unwrap();
+        assert_eq!(player.get_hand().last(), Some(&ActionCard::Upgrade(3)));
+        assert_eq!(
+            player.get_caravan().get_spice_amount(),
+            spice_amount!(2, 2, 0, 0)
+        );
+
+        let card: PointsCard = points_card!(6, [2, 2, 0, 0]);
+        PlayerAction::Score(card).play(&mut player).unwrap();
+        assert_eq!(player.get_score_pile(), &[card]);
+        assert_eq!(
+            player.get_caravan().get_spice_amount(),
+            spice_amount!(0, 0, 0, 0)
+        );
+
+        assert_eq!(
+            PlayerAction::Score(card).play(&mut player),
+            Err(GameErrors::CannotSubtractSpiceAmount(
+                spice_amount!(0, 0, 0, 0),
+                spice_amount!(2, 2, 0, 0)
+            ))
+        );
     }
 }
@@ -0,0 +1,106 @@
+use super::RatingLedger;
+use crate::errors::GameErrors;
+use std::fs;
+use std::path::Path;
+
+/// First word of every ratings file.
+pub const RATINGS_MAGIC: &str = "csr-ratings";
+
+/// Version of the ratings format written by [`RatingLedger::save`].
+pub const RATINGS_FORMAT_VERSION: u32 = 1;
+
+impl RatingLedger {
+    /// Serializes the ledger: a `csr-ratings <version>` header line followed by the players and games as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::ratings::RatingLedger;
+    /// let mut ledger = RatingLedger::new();
+    /// ledger.record(None, &["ann".to_string(), "bo".to_string()]).unwrap();
+    /// let text = ledger.to_ratings_string().unwrap();
+    /// assert!(text.starts_with("csr-ratings 1\n"));
+    /// assert_eq!(RatingLedger::from_ratings_str(&text), Ok(ledger));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidRatingsFile` if the ledger cannot be serialized.
+    pub fn to_ratings_string(&self) -> Result<String, GameErrors> {
+        let ledger = serde_json::to_string(self)
+            .map_err(|error| GameErrors::InvalidRatingsFile(error.to_string()))?;
+        Ok(format!(
+            "{RATINGS_MAGIC} {RATINGS_FORMAT_VERSION}\n{ledger}\n"
+        ))
+    }
+
+    /// Deserializes a ledger written by [`RatingLedger::to_ratings_string`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::InvalidRatingsFile` if the text is not a ratings file of this version.
+    ///
+    /// ```
+    /// use libcsr::{errors::GameErrors, ratings::RatingLedger};
+    /// let result = RatingLedger::from_ratings_str("csr-save 2\n{}\n");
+    /// assert!(matches!(result, Err(GameErrors::InvalidRatingsFile(_))));
+    /// ```
+    pub fn from_ratings_str(text: &str) -> Result<Self, GameErrors> {
+        let (header, ledger) = text.split_once('\n').unwrap_or((text, ""));
+        let expected = format!("{RATINGS_MAGIC} {RATINGS_FORMAT_VERSION}");
+        if header.split_whitespace().collect::<Vec<_>>().join(" ") != expected {
+            return Err(GameErrors::InvalidRatingsFile(format!(
+                "expected a {expected:?} header"
+            )));
+        }
+        serde_json::from_str(ledger)
+            .map_err(|error| GameErrors::InvalidRatingsFile(error.to_string()))
+    }
+
+    /// Saves the ledger to a file, see [`RatingLedger::to_ratings_string`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::RatingsFileIo` if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GameErrors> {
+        fs::write(path, self.to_ratings_string()?)
+            .map_err(|error| GameErrors::RatingsFileIo(error.to_string()))
+    }
+
+    /// Loads a ledger from a file written by [`RatingLedger::save`], or creates an empty one if the file does not
+    /// exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::RatingsFileIo` if the file cannot be read, or any of the errors of
+    /// [`RatingLedger::from_ratings_str`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GameErrors> {
+        if !path.as_ref().exists() {
+            return Ok(Self::new());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|error| GameErrors::RatingsFileIo(error.to_string()))?;
+        Self::from_ratings_str(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ratings::RatingLedger;
+
+    #[test]
+    fn test_save_and_load_file() {
+        let path =
+            std::env::temp_dir().join(format!("csr-ratings-test-{}.txt", std::process::id()));
+        assert_eq!(RatingLedger::load(&path), Ok(RatingLedger::new()));
+
+        let mut ledger = RatingLedger::new();
+        let names = ["ann", "bo", "cy"].map(String::from);
+        ledger.record(Some(3), &names).unwrap();
+        ledger.save(&path).unwrap();
+        let loaded = RatingLedger::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Ok(ledger));
+    }
+}
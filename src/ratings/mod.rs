@@ -0,0 +1,322 @@
+use crate::errors::GameErrors;
+use crate::game::{Game, MAX_PLAYERS, MIN_PLAYERS};
+
+#[cfg(feature = "serde")]
+pub mod file;
+
+/// Rating of a player's first game.
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// Largest change in rating from a single game.
+pub const K_FACTOR: f64 = 32.0;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A player's current rating and record.
+pub struct PlayerRating {
+    pub name: String,
+    pub rating: f64,
+    pub games: usize,
+    pub wins: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How one game changed a player's rating.
+pub struct RatingChange {
+    pub name: String,
+    /// Final position in the game, starting at 1 for the winner.
+    pub rank: usize,
+    pub before: f64,
+    pub after: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A game recorded in a [`RatingLedger`].
+pub struct RatedGame {
+    /// Seed the game was set up from, if any, to find or replay it.
+    pub seed: Option<u64>,
+    /// The change of each player's rating, from the winner to the last player.
+    pub changes: Vec<RatingChange>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The Elo ratings of bots and human players, updated after every completed game.
+///
+/// Games of 3 to 5 players are rated as if each player had played every other one and beaten those ranked below
+/// them: a player's rating moves by [`K_FACTOR`] times their total score minus their expected score against the
+/// others, divided by the number of opponents. Players are identified by name.
+pub struct RatingLedger {
+    players: Vec<PlayerRating>,
+    games: Vec<RatedGame>,
+}
+
+impl RatingLedger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get every rated player, in the order they first played.
+    pub fn get_players(&self) -> &[PlayerRating] {
+        &self.players
+    }
+
+    /// Get every recorded game, oldest first.
+    pub fn get_games(&self) -> &[RatedGame] {
+        &self.games
+    }
+
+    /// Get a player's rating, or [`INITIAL_RATING`] if they have not played yet.
+    pub fn rating(&self, name: &str) -> f64 {
+        self.players
+            .iter()
+            .find(|player| player.name == name)
+            .map_or(INITIAL_RATING, |player| player.rating)
+    }
+
+    /// Get the players from the highest rating to the lowest.
+    pub fn leaderboard(&self) -> Vec<&PlayerRating> {
+        let mut players: Vec<_> = self.players.iter().collect();
+        players.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        players
+    }
+
+    /// Get the changes of a player's rating, oldest first, each with the number of its game starting at 1.
+    pub fn history(&self, name: &str) -> Vec<(usize, &RatingChange)> {
+        self.games
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, game)| {
+                game.changes
+                    .iter()
+                    .filter(|change| change.name == name)
+                    .map(move |change| (idx + 1, change))
+            })
+            .collect()
+    }
+
+    /// Records a game from its players' names, from the winner to the last player, and updates their ratings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::ratings::{RatingLedger, INITIAL_RATING};
+    /// let mut ledger = RatingLedger::new();
+    /// let names = ["ann", "bo", "cy"].map(String::from);
+    /// ledger.record(Some(42), &names).unwrap();
+    /// assert_eq!(ledger.rating("ann"), INITIAL_RATING + 16.0);
+    /// assert_eq!(ledger.rating("bo"), INITIAL_RATING);
+    /// assert_eq!(ledger.rating("cy"), INITIAL_RATING - 16.0);
+    /// assert_eq!(ledger.leaderboard()[0].wins, 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::InvalidNumberOfPlayers` if there are not between [`MIN_PLAYERS`] and [`MAX_PLAYERS`] names.
+    /// * `GameErrors::DuplicatePlayerName` if a name appears twice.
+    pub fn record(
+        &mut self,
+        seed: Option<u64>,
+        ranking: &[String],
+    ) -> Result<&RatedGame, GameErrors> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&ranking.len()) {
+            return Err(GameErrors::InvalidNumberOfPlayers(ranking.len()));
+        }
+        if let Some(name) = ranking
+            .iter()
+            .enumerate()
+            .find_map(|(idx, name)| ranking[..idx].contains(name).then_some(name))
+        {
+            return Err(GameErrors::DuplicatePlayerName(name.clone()));
+        }
+
+        let before: Vec<_> = ranking.iter().map(|name| self.rating(name)).collect();
+        let opponents = (ranking.len() - 1) as f64;
+        let changes = ranking
+            .iter()
+            .enumerate()
+            .map(|(rank, name)| {
+                let surplus: f64 = (0..ranking.len())
+                    .filter(|other| *other != rank)
+                    .map(|other| {
+                        let score = if rank < other { 1.0 } else { 0.0 };
+                        score - expected_score(before[rank], before[other])
+                    })
+                    .sum();
+                RatingChange {
+                    name: name.clone(),
+                    rank: rank + 1,
+                    before: before[rank],
+                    after: before[rank] + K_FACTOR * surplus / opponents,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for change in &changes {
+            let idx = match self.players.iter().position(|x| x.name == change.name) {
+                Some(idx) => idx,
+                None => {
+                    self.players.push(PlayerRating {
+                        name: change.name.clone(),
+                        rating: INITIAL_RATING,
+                        games: 0,
+                        wins: 0,
+                    });
+                    self.players.len() - 1
+                }
+            };
+            let player = &mut self.players[idx];
+            player.rating = change.after;
+            player.games += 1;
+            if change.rank == 1 {
+                player.wins += 1;
+            }
+        }
+
+        self.games.push(RatedGame { seed, changes });
+        self.games.last().ok_or(GameErrors::InternalLogicError)
+    }
+
+    /// Records a completed game, naming its players by seat, and updates their ratings.
+    ///
+    /// The players are ranked by [`Game::standings`].
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::GameNotOver` if the game has not ended.
+    /// * `GameErrors::PlayerNamesMismatch` if there is not one name per player.
+    /// * Any of the errors of [`RatingLedger::record`].
+    ///
+    /// ```
+    /// use libcsr::{errors::GameErrors, game::Game, ratings::RatingLedger};
+    /// let game = Game::new(2, 1).unwrap();
+    /// let names = ["ann", "bo"].map(String::from);
+    /// assert_eq!(RatingLedger::new().record_game(&game, &names), Err(GameErrors::GameNotOver));
+    /// ```
+    pub fn record_game(&mut self, game: &Game, names: &[String]) -> Result<&RatedGame, GameErrors> {
+        if !game.is_over() {
+            return Err(GameErrors::GameNotOver);
+        }
+        self.record_ranked(game, names, None)
+    }
+
+    /// Records a game that a player lost by forfeit, such as a bot breaking its protocol, and updates the ratings.
+    ///
+    /// The forfeiting seat is ranked last and the other players by [`Game::standings`], as the game stands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{game::Game, ratings::RatingLedger};
+    /// let mut ledger = RatingLedger::new();
+    /// let names = ["ann", "bo"].map(String::from);
+    /// let rated = ledger.record_forfeit(&Game::new(2, 1).unwrap(), &names, 1).unwrap();
+    /// assert_eq!(rated.changes[0].name, "ann");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `GameErrors::PlayerNamesMismatch` if there is not one name per player.
+    /// * Any of the errors of [`RatingLedger::record`].
+    pub fn record_forfeit(
+        &mut self,
+        game: &Game,
+        names: &[String],
+        seat: usize,
+    ) -> Result<&RatedGame, GameErrors> {
+        self.record_ranked(game, names, Some(seat))
+    }
+
+    /// Records a game ranked by its standings, with the forfeiting seat last if there is one.
+    fn record_ranked(
+        &mut self,
+        game: &Game,
+        names: &[String],
+        forfeit: Option<usize>,
+    ) -> Result<&RatedGame, GameErrors> {
+        if names.len() != game.get_players().len() {
+            return Err(GameErrors::PlayerNamesMismatch(
+                game.get_players().len(),
+                names.len(),
+            ));
+        }
+        let mut standings = game.standings();
+        standings.sort_by_key(|standing| Some(standing.player) == forfeit);
+        let ranking: Vec<_> = standings
+            .iter()
+            .map(|standing| names[standing.player].clone())
+            .collect();
+        self.record(game.get_seed(), &ranking)
+    }
+}
+
+/// Get the expected score, between 0 and 1, of a player against an opponent.
+fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bots::{greedy::GreedyBot, play_out, random::RandomBot, Bot, DEFAULT_MAX_ROUNDS};
+    use crate::errors::GameErrors;
+    use crate::game::Game;
+    use crate::ratings::{RatingLedger, INITIAL_RATING};
+
+    #[test]
+    fn test_free_for_all() {
+        let mut ledger = RatingLedger::new();
+        let names = ["ann", "bo", "cy", "di", "ed"].map(String::from);
+        ledger.record(None, &names).unwrap();
+        let total: f64 = ledger.get_players().iter().map(|x| x.rating).sum();
+        assert!((total - 5.0 * INITIAL_RATING).abs() < 1e-9);
+        assert_eq!(ledger.leaderboard()[0].name, "ann");
+
+        // An upset moves the ratings more than an expected result.
+        let upset = ledger
+            .record(None, &["ed", "ann"].map(String::from))
+            .unwrap();
+        let gain = upset.changes[0].after - upset.changes[0].before;
+        let expected = ledger
+            .record(None, &["ann", "ed"].map(String::from))
+            .unwrap();
+        assert!(gain > expected.changes[0].after - expected.changes[0].before);
+
+        assert_eq!(ledger.history("ed").len(), 3);
+        assert_eq!(ledger.history("ed")[1].0, 2);
+        assert_eq!(
+            ledger.record(None, &["ann", "ann"].map(String::from)),
+            Err(GameErrors::DuplicatePlayerName("ann".to_string()))
+        );
+        assert_eq!(
+            ledger.record(None, &["ann"].map(String::from)),
+            Err(GameErrors::InvalidNumberOfPlayers(1))
+        );
+    }
+
+    #[test]
+    fn test_record_games() {
+        let mut ledger = RatingLedger::new();
+        let names = ["greedy", "random"].map(String::from);
+        for seed in 0..6 {
+            let mut game = Game::new(2, seed).unwrap();
+            let mut bots: Vec<Box<dyn Bot>> = vec![
+                Box::new(GreedyBot::default()),
+                Box::new(RandomBot::new(seed)),
+            ];
+            play_out(&mut game, &mut bots, DEFAULT_MAX_ROUNDS).unwrap();
+            ledger.record_game(&game, &names).unwrap();
+        }
+
+        assert_eq!(ledger.get_games().len(), 6);
+        assert_eq!(ledger.get_games()[5].seed, Some(5));
+        assert_eq!(ledger.leaderboard()[0].name, "greedy");
+        assert!(ledger.rating("greedy") > INITIAL_RATING + 50.0);
+        assert_eq!(
+            ledger.record_game(&Game::new(2, 0).unwrap(), &names[..1]),
+            Err(GameErrors::GameNotOver)
+        );
+    }
+}
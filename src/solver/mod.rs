@@ -0,0 +1,355 @@
+use crate::cards::{ActionCard, CardChoice, PointsCard};
+use crate::errors::GameErrors;
+use crate::game::MerchantSlot;
+use crate::player::{Caravan, PlayerAction, MAX_CARAVAN_SIZE};
+use crate::spice::SpiceAmount;
+use std::collections::HashSet;
+
+/// Number of turns a [`Solver`] searches by default before giving up.
+pub const DEFAULT_MAX_TURNS: usize = 6;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A shortest sequence of actions found by a [`Solver`].
+pub struct Solution {
+    /// Number of turns taken, not counting the discards made at the end of an overflowing turn.
+    pub turns: usize,
+    /// The actions to take, in order, including the discards.
+    pub actions: Vec<PlayerAction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A position of the search: the caravan, the cards in hand and discarded, counted by their index in
+/// `Solver::cards`, and the merchant row left.
+struct State {
+    caravan: [u8; 4],
+    hand: Vec<u8>,
+    discard_pile: Vec<u8>,
+    market: Vec<(usize, [u8; 4])>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Finds the fewest turns a single player needs to afford a points card, ignoring the other players.
+///
+/// Each turn the player plays a card from their hand, rests, or, if a market is given, acquires one of its cards.
+/// The market is fixed: acquired cards are not replaced, since the deck order is hidden, and only the player's own
+/// payments are left on the cards they skip. The search is breadth-first, so the first solution found is a shortest
+/// one.
+pub struct Solver {
+    /// Every distinct action card the player holds or can acquire.
+    cards: Vec<ActionCard>,
+    start: State,
+    max_turns: usize,
+}
+
+impl Solver {
+    /// Creates a solver for a player with the given hand, discard pile and caravan, and no market.
+    pub fn new(hand: &[ActionCard], discard_pile: &[ActionCard], caravan: &Caravan) -> Self {
+        let mut solver = Self {
+            cards: Vec::new(),
+            start: State {
+                caravan: caravan.get_spice_amount().vector,
+                hand: Vec::new(),
+                discard_pile: Vec::new(),
+                market: Vec::new(),
+            },
+            max_turns: DEFAULT_MAX_TURNS,
+        };
+        for card in hand {
+            let idx = solver.card_index(card);
+            solver.start.hand[idx] += 1;
+        }
+        for card in discard_pile {
+            let idx = solver.card_index(card);
+            solver.start.discard_pile[idx] += 1;
+        }
+        solver
+    }
+
+    /// Allows acquiring the cards of a merchant row, with the spices already on them.
+    pub fn market(mut self, merchant_row: &[MerchantSlot]) -> Self {
+        self.start.market = merchant_row
+            .iter()
+            .map(|slot| (self.card_index(&slot.card), slot.spices.vector))
+            .collect();
+        self
+    }
+
+    /// Sets the number of turns after which the search gives up, [`DEFAULT_MAX_TURNS`] by default.
+    pub fn max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    /// Get the index of a card in `cards`, adding it if it is new.
+    fn card_index(&mut self, card: &ActionCard) -> usize {
+        if let Some(idx) = self.cards.iter().position(|x| x == card) {
+            return idx;
+        }
+        self.cards.push(*card);
+        self.start.hand.push(0);
+        self.start.discard_pile.push(0);
+        self.cards.len() - 1
+    }
+
+    /// Finds a shortest sequence of actions after which the caravan contains the cost of the target card.
+    ///
+    /// Returns `None` if the card cannot be afforded within the maximum number of turns.
+    ///
+    /// # Examples
+    ///
+    /// With the starting cards and 3 turmeric, two cardamon take an upgrade, a rest and another upgrade:
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{action::STARTING_ACTION_CARDS, PointsCard}, player::Caravan, solver::Solver};
+    /// let caravan = Caravan::from_spice_amount(spice_amount!(3, 0, 0, 0)).unwrap();
+    /// let target = PointsCard { points: 8, cost: spice_amount!(0, 0, 2, 0) };
+    /// let solution = Solver::new(&STARTING_ACTION_CARDS, &[], &caravan).solve(&target).unwrap().unwrap();
+    /// assert_eq!(solution.turns, 3);
+    /// let actions: Vec<_> = solution.actions.iter().map(ToString::to_string).collect();
+    /// assert_eq!(actions, ["play U2 Y+1 Y+1", "rest", "play U2 R+1 R+1"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::DiscardPending` if the caravan is overflowing, as the player must discard first.
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, cards::{action::STARTING_ACTION_CARDS, PointsCard}, errors::GameErrors, player::Caravan, solver::Solver};
+    /// let mut caravan = Caravan::from_spice_amount(spice_amount!(10, 0, 0, 0)).unwrap();
+    /// caravan.add(&spice_amount!(2, 0, 0, 0));
+    /// let target = PointsCard { points: 8, cost: spice_amount!(0, 0, 2, 0) };
+    /// let result = Solver::new(&STARTING_ACTION_CARDS, &[], &caravan).solve(&target);
+    /// assert_eq!(result, Err(GameErrors::DiscardPending(2)));
+    /// ```
+    pub fn solve(&self, target: &PointsCard) -> Result<Option<Solution>, GameErrors> {
+        let total = SpiceAmount::from(self.start.caravan).total();
+        if total > MAX_CARAVAN_SIZE {
+            return Err(GameErrors::DiscardPending(total - MAX_CARAVAN_SIZE));
+        }
+
+        let affords = |state: &State| SpiceAmount::from(state.caravan).contains(&target.cost);
+        if affords(&self.start) {
+            return Ok(Some(Solution {
+                turns: 0,
+                actions: Vec::new(),
+            }));
+        }
+
+        // Every state reached, with the node it was reached from and the actions of that turn.
+        let mut nodes = vec![(self.start.clone(), 0, Vec::new())];
+        let mut seen = HashSet::from([self.start.clone()]);
+        let mut frontier = vec![0];
+
+        for turns in 1..=self.max_turns {
+            let mut next = Vec::new();
+            for parent in frontier {
+                for (turn, state) in self.successors(&nodes[parent].0) {
+                    if !seen.insert(state.clone()) {
+                        continue;
+                    }
+                    let found = affords(&state);
+                    nodes.push((state, parent, turn));
+                    if found {
+                        return Ok(Some(Solution {
+                            turns,
+                            actions: Self::path(&nodes, nodes.len() - 1),
+                        }));
+                    }
+                    next.push(nodes.len() - 1);
+                }
+            }
+            frontier = next;
+        }
+        Ok(None)
+    }
+
+    /// Collects the actions leading from the starting state to a node.
+    fn path(nodes: &[(State, usize, Vec<PlayerAction>)], mut node: usize) -> Vec<PlayerAction> {
+        let mut turns = Vec::new();
+        while node != 0 {
+            let (_, parent, turn) = &nodes[node];
+            turns.push(turn.clone());
+            node = *parent;
+        }
+        turns.into_iter().rev().flatten().collect()
+    }
+
+    /// Lists the states reachable in one turn, each with the actions taken.
+    fn successors(&self, state: &State) -> Vec<(Vec<PlayerAction>, State)> {
+        let caravan = SpiceAmount::from(state.caravan);
+        let mut turns = Vec::new();
+
+        for (idx, card) in self.cards.iter().enumerate() {
+            if state.hand[idx] == 0 {
+                continue;
+            }
+            let choices = match card {
+                ActionCard::Gain(_) => vec![CardChoice::Gain],
+                ActionCard::Exchange(input, _) => (1..)
+                    .take_while(|times| {
+                        input
+                            .multiply(*times)
+                            .is_ok_and(|cost| caravan.contains(&cost))
+                    })
+                    .map(CardChoice::Exchange)
+                    .collect(),
+                ActionCard::Upgrade(budget) => caravan
+                    .upgrade_options(*budget)
+                    .into_iter()
+                    .map(|(plan, _)| CardChoice::Upgrade(plan))
+                    .collect(),
+            };
+            for choice in choices {
+                let Ok(result) = card.play(&caravan, &choice) else {
+                    continue;
+                };
+                let mut next = state.clone();
+                next.caravan = result.vector;
+                next.hand[idx] -= 1;
+                next.discard_pile[idx] += 1;
+                turns.push((vec![PlayerAction::PlayCard(*card, choice)], next));
+            }
+        }
+
+        for (slot, (idx, spices)) in state.market.iter().enumerate() {
+            for payment in caravan.selections(slot) {
+                let Ok(remainder) = caravan.subtract(&payment) else {
+                    continue;
+                };
+                let mut next = state.clone();
+                for ((_, skipped), cube) in next.market.iter_mut().zip(payment.cubes()) {
+                    *skipped = SpiceAmount::from(*skipped).add(&cube.into()).vector;
+                }
+                next.market.remove(slot);
+                next.caravan = remainder.add(&SpiceAmount::from(*spices)).vector;
+                next.hand[*idx] += 1;
+                turns.push((
+                    vec![PlayerAction::AcquireCard(self.cards[*idx], payment)],
+                    next,
+                ));
+            }
+        }
+
+        if state.discard_pile.iter().any(|count| *count > 0) {
+            let mut next = state.clone();
+            for (hand, discarded) in next.hand.iter_mut().zip(&mut next.discard_pile) {
+                *hand += std::mem::take(discarded);
+            }
+            turns.push((vec![PlayerAction::Rest], next));
+        }
+
+        turns
+            .into_iter()
+            .flat_map(|(actions, state)| Self::discards(actions, state))
+            .collect()
+    }
+
+    /// Ends a turn whose caravan overflows with each possible discard, or leaves it as it is.
+    fn discards(actions: Vec<PlayerAction>, state: State) -> Vec<(Vec<PlayerAction>, State)> {
+        let caravan = SpiceAmount::from(state.caravan);
+        let total = caravan.total();
+        if total <= MAX_CARAVAN_SIZE {
+            return vec![(actions, state)];
+        }
+
+        caravan
+            .selections(total - MAX_CARAVAN_SIZE)
+            .into_iter()
+            .filter_map(|discard| {
+                let remainder = caravan.subtract(&discard).ok()?;
+                let mut actions = actions.clone();
+                actions.push(PlayerAction::Discard(discard));
+                let mut state = state.clone();
+                state.caravan = remainder.vector;
+                Some((actions, state))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cards::action::{PURCHASABLE_ACTION_CARDS, STARTING_ACTION_CARDS};
+    use crate::cards::points::POINTS_CARDS;
+    use crate::cards::{ActionCard, PointsCard};
+    use crate::game::MerchantSlot;
+    use crate::player::{Caravan, Player, PlayerAction};
+    use crate::solver::{Solution, Solver};
+    use crate::spice::SpiceAmount;
+    use crate::spice_amount;
+
+    /// Plays a solution's actions for a player and checks that they end up affording the target.
+    fn assert_replays(solution: &Solution, mut player: Player, target: &PointsCard) {
+        for action in &solution.actions {
+            action.play(&mut player).unwrap();
+        }
+        assert!(player
+            .get_caravan()
+            .get_spice_amount()
+            .contains(&target.cost));
+    }
+
+    #[test]
+    fn test_market_shortcut() {
+        let caravan = Caravan::from_spice_amount(spice_amount!(3, 0, 0, 0)).unwrap();
+        let target = PointsCard {
+            points: 8,
+            cost: spice_amount!(0, 0, 2, 0),
+        };
+        let market = [
+            MerchantSlot::new(ActionCard::Upgrade(3)),
+            MerchantSlot::new(ActionCard::Gain(spice_amount!(0, 0, 2, 0))),
+        ];
+        let solution = Solver::new(&STARTING_ACTION_CARDS, &[], &caravan)
+            .market(&market)
+            .solve(&target)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(solution.turns, 2);
+        assert_eq!(
+            solution.actions[0].to_string(),
+            "acquire GG Y",
+            "{solution:?}"
+        );
+        assert_replays(&solution, Player::new(1, caravan), &target);
+    }
+
+    #[test]
+    fn test_discards_overflow() {
+        let caravan = Caravan::from_spice_amount(spice_amount!(9, 1, 0, 0)).unwrap();
+        let target = PointsCard {
+            points: 13,
+            cost: spice_amount!(7, 1, 2, 0),
+        };
+        let card = ActionCard::Gain(spice_amount!(0, 0, 2, 0));
+        let mut player = Player::new(1, caravan.clone());
+        PlayerAction::AcquireCard(card, SpiceAmount::default())
+            .play(&mut player)
+            .unwrap();
+        let solution = Solver::new(player.get_hand(), &[], &caravan)
+            .solve(&target)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(solution.turns, 1);
+        assert_eq!(solution.actions[1].to_string(), "discard YY");
+        assert_replays(&solution, player, &target);
+    }
+
+    #[test]
+    fn test_turn_limit() {
+        let caravan = Caravan::from_spice_amount(spice_amount!(4, 1, 0, 0)).unwrap();
+        let market: Vec<_> = PURCHASABLE_ACTION_CARDS[..6]
+            .iter()
+            .map(|card| MerchantSlot::new(*card))
+            .collect();
+        let target = POINTS_CARDS[8];
+        let solver = Solver::new(&STARTING_ACTION_CARDS, &[], &caravan).market(&market);
+        assert_eq!(solver.clone().max_turns(4).solve(&target), Ok(None));
+
+        let solution = solver.solve(&target).unwrap().unwrap();
+        assert_eq!(solution.turns, 5);
+        assert_replays(&solution, Player::new(1, caravan), &target);
+    }
+}
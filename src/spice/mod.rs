@@ -1,7 +1,14 @@
 use crate::errors::GameErrors;
 use crate::spice_amount;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 /// This represents a single spice cube.
 ///
 /// * Turmeric: Level 1 (yellow)
@@ -16,6 +23,14 @@ pub enum SpiceCube {
 }
 
 impl SpiceCube {
+    /// All spices, from least to most valuable.
+    pub const ALL: [SpiceCube; 4] = [
+        SpiceCube::Turmeric,
+        SpiceCube::Saffron,
+        SpiceCube::Cardamon,
+        SpiceCube::Cinnamon,
+    ];
+
     /// Upgrades a _single_ spice cube by the number of steps specified.
     ///
     /// The upgrade process follows the hierarchy defined by the `SpiceCube` enum:
@@ -68,18 +83,98 @@ impl SpiceCube {
         }
 
         let current_level = *self as u8;
-        let target_level = current_level + steps;
+        let target_level = current_level.checked_add(steps);
 
         match target_level {
-            2 => Ok(Self::Saffron),
-            3 => Ok(Self::Cardamon),
-            4 => Ok(Self::Cinnamon),
+            Some(2) => Ok(Self::Saffron),
+            Some(3) => Ok(Self::Cardamon),
+            Some(4) => Ok(Self::Cinnamon),
             _ => Err(GameErrors::CannotUpgradePastCinnamon),
         }
     }
+
+    /// Get the cube written as the given letter, see the `Display` implementation.
+    fn from_letter(letter: char) -> Option<Self> {
+        SpiceCube::ALL
+            .into_iter()
+            .find(|cube| cube.to_string().starts_with(letter))
+    }
+}
+
+/// Writes a spice cube as the initial of its colour: `Y` (turmeric), `R` (saffron), `G` (cardamon) or `B` (cinnamon).
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::spice::SpiceCube;
+/// assert_eq!(SpiceCube::Cardamon.to_string(), "G");
+/// assert_eq!("R".parse(), Ok(SpiceCube::Saffron));
+/// ```
+impl fmt::Display for SpiceCube {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            SpiceCube::Turmeric => 'Y',
+            SpiceCube::Saffron => 'R',
+            SpiceCube::Cardamon => 'G',
+            SpiceCube::Cinnamon => 'B',
+        };
+        write!(f, "{letter}")
+    }
+}
+
+/// Parses the notation written by `Display`.
+///
+/// # Errors
+///
+/// Returns `GameErrors::InvalidNotation` if the string is not a single spice letter.
+///
+/// ```
+/// use libcsr::{errors::GameErrors, spice::SpiceCube};
+/// let result = "YR".parse::<SpiceCube>();
+/// assert_eq!(result, Err(GameErrors::InvalidNotation("YR".to_string(), 2, "expected a single spice".to_string())));
+/// ```
+impl FromStr for SpiceCube {
+    type Err = GameErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut letters = s.chars();
+        match (letters.next(), letters.next()) {
+            (Some(letter), None) => {
+                SpiceCube::from_letter(letter).ok_or_else(|| invalid_spice(s, 1, letter))
+            }
+            (None, _) => Err(GameErrors::InvalidNotation(
+                s.to_string(),
+                1,
+                "expected a spice".to_string(),
+            )),
+            (Some(_), Some(_)) => Err(GameErrors::InvalidNotation(
+                s.to_string(),
+                2,
+                "expected a single spice".to_string(),
+            )),
+        }
+    }
 }
 
+fn invalid_spice(s: &str, column: usize, letter: char) -> GameErrors {
+    GameErrors::InvalidNotation(
+        s.to_string(),
+        column,
+        format!("{letter:?} is not a spice, expected one of Y, R, G or B"),
+    )
+}
+
+/// A plan for an `Upgrade` card: the cubes to upgrade, each with its number of steps.
+///
+/// Entries are applied in order, so a cube upgraded by one entry can be upgraded again by a later one.
+pub type UpgradePlan = Vec<(SpiceCube, u8)>;
+
 #[derive(Debug, Default, PartialEq, Copy, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "SpiceAmountRepr", into = "SpiceAmountRepr")
+)]
 /// Represents an amount of spices.
 ///
 /// The vector field contains duplicate information but having the separate fields makes it easier to work with and build amounts to avoid indexing errors.
@@ -115,11 +210,11 @@ impl SpiceAmount {
             && self.cardamon >= other.cardamon
             && self.cinnamon >= other.cinnamon
     }
-    
+
     /// Adds another `SpiceAmount` to this `SpiceAmount`.
-    /// 
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```
     /// use libcsr::{spice_amount, spice::SpiceAmount};
     /// let first_amount = spice_amount!(2, 1, 4, 3);
@@ -137,6 +232,35 @@ impl SpiceAmount {
         )
     }
 
+    /// Multiplies every spice in this `SpiceAmount` by a factor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::SpiceAmount};
+    /// let spice_amount = spice_amount!(2, 1, 0, 0);
+    /// assert_eq!(spice_amount.multiply(3), Ok(spice_amount!(6, 3, 0, 0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::SpiceAmountOverflow` if any spice would exceed `u8::MAX`.
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::SpiceAmount, errors::GameErrors};
+    /// let spice_amount = spice_amount!(2, 0, 0, 0);
+    /// assert_eq!(spice_amount.multiply(128), Err(GameErrors::SpiceAmountOverflow));
+    /// ```
+    pub fn multiply(&self, factor: u8) -> Result<SpiceAmount, GameErrors> {
+        let mut vector = [0u8; 4];
+        for (product, x) in vector.iter_mut().zip(self.vector) {
+            *product = x
+                .checked_mul(factor)
+                .ok_or(GameErrors::SpiceAmountOverflow)?;
+        }
+        Ok(SpiceAmount::from(vector))
+    }
+
     /// Attempt to subtract another `SpiceAmount` from this `SpiceAmount`.
     ///
     /// # Examples
@@ -182,6 +306,301 @@ impl SpiceAmount {
             self.cinnamon - other.cinnamon
         ))
     }
+
+    /// Upgrades a _single_ cube of the given spice within this `SpiceAmount` by the number of steps specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube}};
+    /// let spice_amount = spice_amount!(2, 1, 0, 0);
+    /// let result = spice_amount.upgrade(SpiceCube::Turmeric, 2).unwrap();
+    /// assert_eq!(result, spice_amount!(1, 1, 1, 0));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::CannotSubtractSpiceAmount` if there is no cube of the given spice, or any of the errors of [`SpiceCube::upgrade`].
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube}, errors::GameErrors};
+    /// let spice_amount = spice_amount!(2, 0, 0, 0);
+    /// let result = spice_amount.upgrade(SpiceCube::Saffron, 1);
+    /// assert_eq!(result, Err(GameErrors::CannotSubtractSpiceAmount(spice_amount, spice_amount!(0, 1, 0, 0))));
+    /// ```
+    pub fn upgrade(self, cube: SpiceCube, steps: u8) -> Result<Self, GameErrors> {
+        let upgraded_cube = cube.upgrade(steps)?;
+        let remainder = self.subtract(&SpiceAmount::from(cube))?;
+        Ok(remainder.add(&SpiceAmount::from(upgraded_cube)))
+    }
+
+    /// Upgrades several cubes within this `SpiceAmount` following an [`UpgradePlan`], spending at most `budget` steps.
+    ///
+    /// # Examples
+    ///
+    /// Split 3 steps between two turmeric cubes:
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube::Turmeric}};
+    /// let spice_amount = spice_amount!(2, 0, 0, 0);
+    /// let result = spice_amount.apply_upgrades(&[(Turmeric, 2), (Turmeric, 1)], 3);
+    /// assert_eq!(result, Ok(spice_amount!(0, 1, 1, 0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameErrors::UpgradeBudgetExceeded` if the plan uses more steps than the budget.
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube::Turmeric}, errors::GameErrors};
+    /// let spice_amount = spice_amount!(2, 0, 0, 0);
+    /// let result = spice_amount.apply_upgrades(&[(Turmeric, 2), (Turmeric, 1)], 2);
+    /// assert_eq!(result, Err(GameErrors::UpgradeBudgetExceeded(2)));
+    /// ```
+    ///
+    /// Returns any of the errors of [`SpiceAmount::upgrade`] for the first entry that cannot be applied, e.g. one
+    /// that would take a cube past Cinnamon.
+    pub fn apply_upgrades(
+        self,
+        upgrades: &[(SpiceCube, u8)],
+        budget: u8,
+    ) -> Result<Self, GameErrors> {
+        let within_budget = upgrades
+            .iter()
+            .try_fold(0u8, |total, (_, steps)| total.checked_add(*steps))
+            .is_some_and(|total| total <= budget);
+        if !within_budget {
+            return Err(GameErrors::UpgradeBudgetExceeded(budget));
+        }
+
+        upgrades
+            .iter()
+            .try_fold(self, |amount, (cube, steps)| amount.upgrade(*cube, *steps))
+    }
+
+    /// Lists every distinct `SpiceAmount` reachable by upgrading cubes in this `SpiceAmount` with at most `budget`
+    /// steps, together with an [`UpgradePlan`] reaching it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube::Turmeric}};
+    /// let spice_amount = spice_amount!(2, 0, 0, 0);
+    /// let results: Vec<_> = spice_amount.upgrade_options(2).into_iter().map(|(_, result)| result).collect();
+    /// assert_eq!(results.len(), 3);
+    /// assert!(results.contains(&spice_amount!(1, 1, 0, 0)));
+    /// assert!(results.contains(&spice_amount!(0, 2, 0, 0)));
+    /// assert!(results.contains(&spice_amount!(1, 0, 1, 0)));
+    /// ```
+    pub fn upgrade_options(&self, budget: u8) -> Vec<(UpgradePlan, SpiceAmount)> {
+        let mut options = Vec::new();
+        self.push_upgrade_options(0, 0, budget, budget, &mut Vec::new(), &mut options);
+        options
+    }
+
+    /// Recursively extends `plan` with upgrades of the cubes at `level` and above, recording each plan's result.
+    ///
+    /// Cubes of the same spice are interchangeable, so their steps are chosen in non-increasing order and `used`
+    /// counts how many of the cubes at `level` are already in the plan.
+    fn push_upgrade_options(
+        &self,
+        level: usize,
+        used: u8,
+        max_steps: u8,
+        budget: u8,
+        plan: &mut UpgradePlan,
+        options: &mut Vec<(UpgradePlan, SpiceAmount)>,
+    ) {
+        if let Some(Ok(result)) = (!plan.is_empty()).then(|| self.apply_upgrades(plan, u8::MAX)) {
+            if options.iter().all(|(_, x)| *x != result) {
+                options.push((plan.clone(), result));
+            }
+        }
+
+        // Cinnamon cubes cannot be upgraded.
+        for next_level in level..SpiceCube::ALL.len() - 1 {
+            let (used, max_steps) = if next_level == level {
+                (used, max_steps)
+            } else {
+                (0, u8::MAX)
+            };
+            if used >= self.vector[next_level] {
+                continue;
+            }
+
+            let cube = SpiceCube::ALL[next_level];
+            let highest = (SpiceCube::Cinnamon as usize - cube as usize) as u8;
+            for steps in 1..=max_steps.min(budget).min(highest) {
+                plan.push((cube, steps));
+                self.push_upgrade_options(
+                    next_level,
+                    used + 1,
+                    steps,
+                    budget - steps,
+                    plan,
+                    options,
+                );
+                plan.pop();
+            }
+        }
+    }
+
+    /// Lists every distinct `SpiceAmount` of exactly `count` cubes contained in this `SpiceAmount`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::SpiceAmount};
+    /// let spice_amount = spice_amount!(2, 1, 0, 0);
+    /// let selections = spice_amount.selections(2);
+    /// assert_eq!(selections, vec![spice_amount!(2, 0, 0, 0), spice_amount!(1, 1, 0, 0)]);
+    /// ```
+    pub fn selections(&self, count: usize) -> Vec<SpiceAmount> {
+        let mut selections = Vec::new();
+        let mut selection = [0; 4];
+        self.push_selections(0, count, &mut selection, &mut selections);
+        selections
+    }
+
+    /// Recursively chooses how many cubes of the spice at `level` and above go into `selection`.
+    fn push_selections(
+        &self,
+        level: usize,
+        count: usize,
+        selection: &mut [u8; 4],
+        selections: &mut Vec<SpiceAmount>,
+    ) {
+        if level == selection.len() {
+            if count == 0 {
+                selections.push(SpiceAmount::from(*selection));
+            }
+            return;
+        }
+
+        for amount in (0..=count.min(self.vector[level] as usize)).rev() {
+            selection[level] = amount as u8;
+            self.push_selections(level + 1, count - amount, selection, selections);
+        }
+        selection[level] = 0;
+    }
+
+    /// Iterate over the individual cubes in this `SpiceAmount`, from least to most valuable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube::{Turmeric, Cinnamon}}};
+    /// let spice_amount = spice_amount!(2, 0, 0, 1);
+    /// let cubes: Vec<_> = spice_amount.cubes().collect();
+    /// assert_eq!(cubes, vec![Turmeric, Turmeric, Cinnamon]);
+    /// ```
+    pub fn cubes(&self) -> impl Iterator<Item = SpiceCube> {
+        SpiceCube::ALL
+            .into_iter()
+            .zip(self.vector)
+            .flat_map(|(cube, count)| std::iter::repeat_n(cube, count as usize))
+    }
+
+    /// Get the total number of spice cubes in this `SpiceAmount`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{spice_amount, spice::SpiceAmount};
+    /// let spice_amount = spice_amount!(2, 1, 0, 3);
+    /// assert_eq!(spice_amount.total(), 6);
+    /// assert_eq!(spice_amount!(200, 57, 0, 0).total(), 257);
+    /// ```
+    pub fn total(&self) -> usize {
+        self.vector.iter().map(|&count| count as usize).sum()
+    }
+}
+
+/// Writes a spice amount as one letter per cube, from least to most valuable (see [`SpiceCube`]), or `-` if empty.
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{spice_amount, spice::SpiceAmount};
+/// assert_eq!(spice_amount!(2, 1, 0, 0).to_string(), "YYR");
+/// assert_eq!(SpiceAmount::default().to_string(), "-");
+/// assert_eq!("BYY".parse(), Ok(spice_amount!(2, 0, 0, 1)));
+/// ```
+impl fmt::Display for SpiceAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.total() == 0 {
+            return write!(f, "-");
+        }
+        self.cubes().try_for_each(|cube| write!(f, "{cube}"))
+    }
+}
+
+/// Parses the notation written by `Display`. The letters may come in any order.
+///
+/// # Errors
+///
+/// Returns `GameErrors::InvalidNotation` with the column of the first character that is not a spice letter.
+///
+/// ```
+/// use libcsr::{errors::GameErrors, spice::SpiceAmount};
+/// let result = "YYx".parse::<SpiceAmount>();
+/// assert_eq!(result, Err(GameErrors::InvalidNotation("YYx".to_string(), 3, "'x' is not a spice, expected one of Y, R, G or B".to_string())));
+/// ```
+impl FromStr for SpiceAmount {
+    type Err = GameErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            return Ok(SpiceAmount::default());
+        }
+        if s.is_empty() {
+            return Err(GameErrors::InvalidNotation(
+                s.to_string(),
+                1,
+                "expected spices, or - for none".to_string(),
+            ));
+        }
+
+        let mut vector = [0u8; 4];
+        for (idx, letter) in s.chars().enumerate() {
+            let cube =
+                SpiceCube::from_letter(letter).ok_or_else(|| invalid_spice(s, idx + 1, letter))?;
+            let count = &mut vector[cube as usize - 1];
+            *count = count.checked_add(1).ok_or_else(|| {
+                GameErrors::InvalidNotation(s.to_string(), idx + 1, "too many spices".to_string())
+            })?;
+        }
+        Ok(SpiceAmount::from(vector))
+    }
+}
+
+/// The serialized form of a `SpiceAmount`, leaving out the redundant `vector` field.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SpiceAmountRepr {
+    turmeric: u8,
+    saffron: u8,
+    cardamon: u8,
+    cinnamon: u8,
+}
+
+#[cfg(feature = "serde")]
+impl From<SpiceAmountRepr> for SpiceAmount {
+    fn from(repr: SpiceAmountRepr) -> Self {
+        spice_amount!(repr.turmeric, repr.saffron, repr.cardamon, repr.cinnamon)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SpiceAmount> for SpiceAmountRepr {
+    fn from(spice_amount: SpiceAmount) -> Self {
+        Self {
+            turmeric: spice_amount.turmeric,
+            saffron: spice_amount.saffron,
+            cardamon: spice_amount.cardamon,
+            cinnamon: spice_amount.cinnamon,
+        }
+    }
 }
 
 /// A builder for the `SpiceAmount` struct.
@@ -274,4 +693,72 @@ impl From<SpiceAmount> for [u8; 4] {
     fn from(spice_amount: SpiceAmount) -> [u8; 4] {
         spice_amount.vector
     }
-}
\ No newline at end of file
+}
+
+/// Create a `SpiceAmount` holding a single cube of the given spice.
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{spice_amount, spice::{SpiceAmount, SpiceCube}};
+/// let spice_amount = SpiceAmount::from(SpiceCube::Cardamon);
+/// assert_eq!(spice_amount, spice_amount!(0, 0, 1, 0));
+/// ```
+impl From<SpiceCube> for SpiceAmount {
+    fn from(cube: SpiceCube) -> Self {
+        let mut spice_array = [0; 4];
+        spice_array[cube as usize - 1] = 1;
+        Self::from(spice_array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::GameErrors;
+    use crate::spice::SpiceCube;
+    use crate::spice_amount;
+
+    #[test]
+    fn test_upgrade_steps_overflow() {
+        assert_eq!(
+            SpiceCube::Saffron.upgrade(u8::MAX),
+            Err(GameErrors::CannotUpgradePastCinnamon)
+        );
+        assert_eq!(
+            spice_amount!(2, 0, 0, 0)
+                .apply_upgrades(&[(SpiceCube::Turmeric, 255), (SpiceCube::Turmeric, 2)], 2),
+            Err(GameErrors::UpgradeBudgetExceeded(2))
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::spice::{SpiceAmount, SpiceCube};
+    use crate::spice_amount;
+
+    #[test]
+    fn test_spice_amount_json() {
+        let spice_amount = spice_amount!(1, 2, 0, 3);
+        let json = serde_json::to_string(&spice_amount).unwrap();
+        assert_eq!(
+            json,
+            r#"{"turmeric":1,"saffron":2,"cardamon":0,"cinnamon":3}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<SpiceAmount>(&json).unwrap(),
+            spice_amount
+        );
+    }
+
+    #[test]
+    fn test_spice_amount_json_rebuilds_vector() {
+        let json = r#"{"turmeric":4,"saffron":0,"cardamon":1,"cinnamon":0}"#;
+        let spice_amount: SpiceAmount = serde_json::from_str(json).unwrap();
+        assert_eq!(spice_amount.vector, [4, 0, 1, 0]);
+        assert_eq!(
+            serde_json::to_string(&SpiceCube::Cardamon).unwrap(),
+            r#""cardamon""#
+        );
+    }
+}
@@ -0,0 +1,341 @@
+use libcsr::bots::Bot;
+use libcsr::errors::GameErrors;
+use libcsr::game::{scoring::Standing, view::GameView, Game};
+use libcsr::net::{Client, ServerMessage};
+use libcsr::player::PlayerAction;
+use libcsr::ratings::RatingLedger;
+use libcsr::tournament::Forfeit;
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "Enter the number of a move, or a move in card notation (e.g. \"play YYY>GG x2\",
+\"play U2 Y+1 Y+1\", \"acquire U3 YY\", \"rest\", \"score 12:RRGG\", \"discard Y\").
+Other commands: \"moves\" lists the legal moves again, \"save <file>\" saves the game, \"quit\" stops playing.";
+
+const JOIN_HELP: &str =
+    "Enter the number of a move, or a move in card notation (e.g. \"play YYY>GG x2\",
+\"play U2 Y+1 Y+1\", \"acquire U3 YY\", \"rest\", \"score 12:RRGG\", \"discard Y\").
+Other commands: \"moves\" lists the legal moves again, \"quit\" leaves the game.";
+
+/// Runs a hot-seat game on stdin and stdout until it ends or the players quit.
+///
+/// Seats with a bot are played by the bot, the others are prompted for. If a save file is given, the game is saved
+/// to it after every move. Saves record `seats`, describing who plays each seat, so that resuming brings the same
+/// bots and names back. Returns the game as it was left, over or not, and the bot that forfeited it if one did.
+pub fn play(
+    mut game: Game,
+    mut bots: Vec<Option<Box<dyn Bot>>>,
+    seats: &[String],
+    save_path: Option<&str>,
+) -> Result<(Game, Option<Forfeit>), GameErrors> {
+    println!("{HELP}");
+    let mut lines = io::stdin().lock().lines();
+
+    while !game.is_over() {
+        let player = game.get_current_player_index() + 1;
+        if let Some(bot) = &mut bots[player - 1] {
+            let action = match bot.choose_action(&game.view()) {
+                Err(GameErrors::BotForfeit(name, reason)) => {
+                    println!("Player {player} ({name}) forfeits: it {reason}");
+                    let seat = player - 1;
+                    return Ok((game, Some(Forfeit { seat, reason })));
+                }
+                action => action?,
+            };
+            println!("Player {player} ({}): {action}", bot.name());
+            game.play(action)?;
+            if let Some(path) = save_path {
+                game.save_with_seats(path, seats)?;
+            }
+            continue;
+        }
+
+        println!();
+        print_game(&game);
+        let moves = game.legal_actions();
+        print_moves(&moves);
+
+        loop {
+            print!("Player {player}> ");
+            io::stdout().flush().ok();
+            let Some(Ok(line)) = lines.next() else {
+                println!();
+                return Ok((game, None));
+            };
+
+            let action = match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => continue,
+                ["quit"] => return Ok((game, None)),
+                ["help"] => {
+                    println!("{HELP}");
+                    continue;
+                }
+                ["moves"] => {
+                    print_moves(&moves);
+                    continue;
+                }
+                ["save", path] => {
+                    match game.save_with_seats(path, seats) {
+                        Ok(()) => println!("Saved to {path}."),
+                        Err(error) => println!("{error}"),
+                    }
+                    continue;
+                }
+                _ => match parse_move(&line, &moves) {
+                    Ok(action) => action,
+                    Err(error) => {
+                        println!("{error}");
+                        continue;
+                    }
+                },
+            };
+
+            match game.play(action.clone()) {
+                Ok(()) => {
+                    println!("Player {player}: {action}");
+                    break;
+                }
+                Err(error) => println!("{error}"),
+            }
+        }
+
+        if let Some(path) = save_path {
+            game.save_with_seats(path, seats)?;
+        }
+    }
+
+    println!();
+    print_game(&game);
+    print_standings(&game.standings());
+    Ok((game, None))
+}
+
+/// Plays a networked game on stdin and stdout as the client's player, until it ends, a player leaves or the player
+/// quits.
+pub fn join(mut client: Client) -> Result<(), GameErrors> {
+    let player = client.get_seat() + 1;
+    println!("Joined as player {player} of {}.", client.get_players());
+    println!("{JOIN_HELP}");
+    let mut lines = io::stdin().lock().lines();
+    let mut state: Option<GameView> = None;
+
+    loop {
+        match client.receive()? {
+            ServerMessage::Welcome { .. } => continue,
+            ServerMessage::Lobby { names } => {
+                println!(
+                    "Players: {} ({} of {}).",
+                    names.join(", "),
+                    names.len(),
+                    client.get_players()
+                );
+                continue;
+            }
+            ServerMessage::State { view, last } => {
+                if let Some((seat, action)) = &last {
+                    println!("Player {}: {action}", seat + 1);
+                }
+                if last.is_none() || !view.legal_actions.is_empty() {
+                    println!();
+                    print_view(&view, false);
+                    if !view.legal_actions.is_empty() {
+                        print_moves(&view.legal_actions);
+                    }
+                }
+                state = Some(view);
+            }
+            ServerMessage::Rejected { reason } => println!("{reason}"),
+            ServerMessage::GameOver { standings } => {
+                if let Some(view) = &state {
+                    println!();
+                    print_view(view, true);
+                }
+                print_standings(&standings);
+                return Ok(());
+            }
+            ServerMessage::Left { name } => {
+                println!("{name} left the game.");
+                return Ok(());
+            }
+        }
+
+        let Some(view) = state.as_ref().filter(|view| !view.legal_actions.is_empty()) else {
+            continue;
+        };
+        let action = loop {
+            print!("Player {player}> ");
+            io::stdout().flush().ok();
+            let Some(Ok(line)) = lines.next() else {
+                println!();
+                return Ok(());
+            };
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => {}
+                ["quit"] => return Ok(()),
+                ["help"] => println!("{JOIN_HELP}"),
+                ["moves"] => print_moves(&view.legal_actions),
+                _ => match parse_move(&line, &view.legal_actions) {
+                    Ok(action) => break action,
+                    Err(error) => println!("{error}"),
+                },
+            }
+        };
+        client.play(action)?;
+    }
+}
+
+/// Parses a move entered by a player: the number of a legal move, or a move in card notation.
+fn parse_move(line: &str, moves: &[PlayerAction]) -> Result<PlayerAction, String> {
+    let line = line.trim();
+    if line.bytes().all(|byte| byte.is_ascii_digit()) {
+        return line
+            .parse()
+            .ok()
+            .and_then(|n: usize| moves.get(n.wrapping_sub(1)))
+            .cloned()
+            .ok_or(format!(
+                "There is no move {line}, enter \"moves\" to list them."
+            ));
+    }
+    line.parse().map_err(|error: GameErrors| error.to_string())
+}
+
+/// Prints the market rows and every player's cards and spices.
+pub fn print_game(game: &Game) {
+    if let Some(seed) = game.get_seed() {
+        println!("Seed {seed}");
+    }
+    print_view(&game.view(), game.is_over());
+}
+
+/// Prints the market rows and every player's cards and spices, as seen by a player.
+fn print_view(view: &GameView, over: bool) {
+    println!("Points cards ({} left in the deck):", view.points_deck_len);
+    let coins = view.coins;
+    for (idx, card) in view.points_row.iter().enumerate() {
+        match coins.coin_for_slot(idx) {
+            Some(coin) => println!("  {}. {:<10} + {coin:?} coin", idx + 1, card.to_string()),
+            None => println!("  {}. {card}", idx + 1),
+        }
+    }
+    println!("  Coins left: {} gold, {} silver", coins.gold, coins.silver);
+
+    println!(
+        "Merchant cards ({} left in the deck):",
+        view.merchant_deck_len
+    );
+    for (idx, slot) in view.merchant_row.iter().enumerate() {
+        if slot.spices.total() > 0 {
+            println!(
+                "  {}. {:<10} + {}",
+                idx + 1,
+                slot.card.to_string(),
+                slot.spices
+            );
+        } else {
+            println!("  {}. {}", idx + 1, slot.card);
+        }
+    }
+
+    for (idx, player) in view.players.iter().enumerate() {
+        let marker = if idx == view.seat && !over { "*" } else { " " };
+        println!(
+            "{marker} Player {}: caravan {}, score {}",
+            player.get_player_order(),
+            player.get_caravan().get_spice_amount(),
+            player.score()
+        );
+        println!("    hand:    {}", list(player.get_hand()));
+        println!("    discard: {}", list(player.get_discard_pile()));
+        println!(
+            "    scored:  {}, coins: {}",
+            list(player.get_score_pile()),
+            list(
+                &player
+                    .get_coins()
+                    .iter()
+                    .map(|coin| format!("{coin:?}"))
+                    .collect::<Vec<_>>()
+            )
+        );
+    }
+    if view.end_triggered && !over {
+        println!("Last round!");
+    }
+}
+
+fn print_moves(moves: &[PlayerAction]) {
+    println!("Legal moves:");
+    for (idx, action) in moves.iter().enumerate() {
+        println!("  {:>3}. {action}", idx + 1);
+    }
+}
+
+fn print_standings(standings: &[Standing]) {
+    println!("Final standings:");
+    for standing in standings {
+        println!(
+            "  {}. Player {}: {} points ({} from {} points card(s), {} from coins, {} from spices)",
+            standing.rank,
+            standing.player + 1,
+            standing.total,
+            standing.card_points,
+            standing.points_cards,
+            standing.coin_points,
+            standing.spice_points
+        );
+    }
+}
+
+/// Prints the rated players from the highest rating to the lowest.
+pub fn print_leaderboard(ledger: &RatingLedger) {
+    if ledger.get_players().is_empty() {
+        println!("No rated games yet.");
+        return;
+    }
+    println!(
+        "{:>4}  {:<20} {:>6} {:>6} {:>6}",
+        "Rank", "Player", "Rating", "Games", "Wins"
+    );
+    for (idx, player) in ledger.leaderboard().iter().enumerate() {
+        println!(
+            "{:>4}  {:<20} {:>6.0} {:>6} {:>6}",
+            idx + 1,
+            player.name,
+            player.rating,
+            player.games,
+            player.wins
+        );
+    }
+}
+
+/// Prints every change of a player's rating, oldest first.
+pub fn print_rating_history(ledger: &RatingLedger, name: &str) {
+    let history = ledger.history(name);
+    if history.is_empty() {
+        println!("{name} has no rated games.");
+        return;
+    }
+    println!(
+        "{:>6}  {:>4}  {:>6}  {:>6}",
+        "Game", "Rank", "Before", "After"
+    );
+    for (game, change) in history {
+        println!(
+            "{game:>6}  {:>4}  {:>6.0}  {:>6.0}",
+            change.rank, change.before, change.after
+        );
+    }
+}
+
+/// Joins items with spaces, or `-` if there are none.
+fn list<T: Display>(items: &[T]) -> String {
+    if items.is_empty() {
+        return "-".to_string();
+    }
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,597 @@
+use crate::bots::{Bot, DEFAULT_MAX_ROUNDS};
+use crate::errors::GameErrors;
+use crate::game::{Game, MAX_PLAYERS, MIN_PLAYERS};
+use crate::player::PlayerAction;
+use crate::rng::Rng;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Normal quantile for the 95% confidence intervals of the statistics.
+const Z_95: f64 = 1.96;
+
+/// Creates an entrant's bot for one game, from a seed drawn for that game and seat.
+pub type BotFactory = Box<dyn Fn(u64) -> Box<dyn Bot> + Send + Sync>;
+
+/// A bot taking part in a tournament, created afresh for every game.
+pub struct Entrant {
+    name: String,
+    factory: BotFactory,
+}
+
+impl Entrant {
+    /// Creates an entrant with a name for the results and a function creating its bot from a seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::{bots::random::RandomBot, tournament::Entrant};
+    /// let entrant = Entrant::new("random", |seed| Box::new(RandomBot::new(seed)));
+    /// assert_eq!(entrant.get_name(), "random");
+    /// ```
+    pub fn new(
+        name: impl Into<String>,
+        factory: impl Fn(u64) -> Box<dyn Bot> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            factory: Box::new(factory),
+        }
+    }
+
+    /// Get the entrant's name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for Entrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entrant").field("name", &self.name).finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The settings of a tournament, see [`run`].
+pub struct TournamentConfig {
+    /// Number of games to play, rounded up to a multiple of the number of entrants, see [`run`].
+    pub games: usize,
+    /// Master seed, from which every game's setup and bots are seeded.
+    pub seed: u64,
+    /// Number of games played at the same time, or 0 for one per CPU core.
+    pub threads: usize,
+    /// Number of rounds after which a game is stopped and scored as it stands, in case the bots never end it.
+    pub max_rounds: usize,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            games: 1000,
+            seed: 0,
+            threads: 0,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The outcome of one tournament game.
+pub struct GameResult {
+    /// Seed the game was set up from.
+    pub seed: u64,
+    /// Index of the entrant playing each seat.
+    pub seats: Vec<usize>,
+    /// Final score of each seat.
+    pub scores: Vec<u32>,
+    /// Seat of the winner, see [`Game::standings`].
+    pub winner: usize,
+    /// Number of rounds played, counting the last one even if it was cut short.
+    pub rounds: usize,
+    /// Whether the game ended normally rather than at the round limit or by a forfeit.
+    pub finished: bool,
+    /// The bot that forfeited the game, if one did.
+    pub forfeit: Option<Forfeit>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A bot losing a game by breaking the rules of its protocol, see `GameErrors::BotForfeit`.
+pub struct Forfeit {
+    /// Seat of the bot, which is ranked last.
+    pub seat: usize,
+    /// What the bot did wrong.
+    pub reason: String,
+}
+
+impl GameResult {
+    /// Get the seats from the winner to the last player, ranked as by [`Game::standings`] except for a forfeiting
+    /// bot, which is last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::tournament::{Forfeit, GameResult};
+    /// let mut result = GameResult {
+    ///     seed: 1, seats: vec![2, 0, 1], scores: vec![40, 52, 40], winner: 1, rounds: 20, finished: true, forfeit: None,
+    /// };
+    /// assert_eq!(result.ranking(), [1, 2, 0]);
+    /// result.forfeit = Some(Forfeit { seat: 1, reason: "did not move within 5000 ms".to_string() });
+    /// assert_eq!(result.ranking(), [2, 0, 1]);
+    /// ```
+    pub fn ranking(&self) -> Vec<usize> {
+        let forfeit = self.forfeit.as_ref().map(|forfeit| forfeit.seat);
+        let mut seats: Vec<_> = (0..self.scores.len()).collect();
+        seats.sort_by(|a, b| {
+            (Some(*a) == forfeit)
+                .cmp(&(Some(*b) == forfeit))
+                .then(self.scores[*b].cmp(&self.scores[*a]))
+                .then(b.cmp(a))
+        });
+        seats
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An estimated value with its 95% confidence interval.
+pub struct Estimate {
+    /// The value measured over the games played.
+    pub mean: f64,
+    /// Lower bound of the confidence interval.
+    pub low: f64,
+    /// Upper bound of the confidence interval.
+    pub high: f64,
+}
+
+impl Estimate {
+    /// Estimates a proportion from a number of successes out of a number of trials, with the Wilson score interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::tournament::Estimate;
+    /// let estimate = Estimate::proportion(80, 100);
+    /// assert_eq!(estimate.mean, 0.8);
+    /// assert!((estimate.low - 0.711).abs() < 0.001 && (estimate.high - 0.866).abs() < 0.001);
+    /// ```
+    pub fn proportion(successes: usize, trials: usize) -> Self {
+        if trials == 0 {
+            return Self {
+                mean: 0.0,
+                low: 0.0,
+                high: 1.0,
+            };
+        }
+        let n = trials as f64;
+        let p = successes as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+        Self {
+            mean: p,
+            low: (center - half).max(0.0),
+            high: (center + half).min(1.0),
+        }
+    }
+
+    /// Estimates the mean of a sample, with the normal approximation of its standard error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libcsr::tournament::Estimate;
+    /// let estimate = Estimate::mean(&[2.0, 4.0, 6.0, 8.0]);
+    /// assert_eq!(estimate.mean, 5.0);
+    /// assert!((estimate.high - 5.0 - 1.96 * (20.0f64 / 3.0 / 4.0).sqrt()).abs() < 1e-9);
+    /// ```
+    pub fn mean(values: &[f64]) -> Self {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n.max(1.0);
+        if values.len() < 2 {
+            return Self {
+                mean,
+                low: mean,
+                high: mean,
+            };
+        }
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let half = Z_95 * (variance / n).sqrt();
+        Self {
+            mean,
+            low: mean - half,
+            high: mean + half,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An entrant's results over a tournament.
+pub struct EntrantStats {
+    pub name: String,
+    /// Number of games played, which is every game of the tournament.
+    pub games: usize,
+    pub wins: usize,
+    /// Number of games lost by forfeit.
+    pub forfeits: usize,
+    pub win_rate: Estimate,
+    /// The final score, including the points from coins and spices.
+    pub score: Estimate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The results of a tournament, see [`run`].
+pub struct TournamentReport {
+    /// Every game, in the order they were drawn from the master seed.
+    pub games: Vec<GameResult>,
+    /// The results of each entrant, in the order they were entered.
+    pub entrants: Vec<EntrantStats>,
+    /// The win rate of each seat, whoever plays it, which shows the advantage of playing first or last.
+    pub seat_win_rates: Vec<Estimate>,
+    /// The length of the games, in rounds.
+    pub rounds: Estimate,
+    /// Number of games stopped at the round limit.
+    pub unfinished: usize,
+}
+
+impl TournamentReport {
+    /// Computes the statistics of a set of games between the named entrants.
+    pub fn new(names: &[String], games: Vec<GameResult>) -> Self {
+        let entrants = names
+            .iter()
+            .enumerate()
+            .map(|(entrant, name)| {
+                let scores: Vec<_> = games
+                    .iter()
+                    .flat_map(|game| {
+                        game.seats
+                            .iter()
+                            .zip(&game.scores)
+                            .filter(|(seated, _)| **seated == entrant)
+                            .map(|(_, score)| *score as f64)
+                    })
+                    .collect();
+                let wins = games
+                    .iter()
+                    .filter(|game| game.seats[game.winner] == entrant)
+                    .count();
+                let forfeits = games
+                    .iter()
+                    .filter_map(|game| game.forfeit.as_ref().map(|x| game.seats[x.seat]))
+                    .filter(|seated| *seated == entrant)
+                    .count();
+                EntrantStats {
+                    name: name.clone(),
+                    games: scores.len(),
+                    wins,
+                    forfeits,
+                    win_rate: Estimate::proportion(wins, scores.len()),
+                    score: Estimate::mean(&scores),
+                }
+            })
+            .collect();
+
+        let seat_win_rates = (0..names.len())
+            .map(|seat| {
+                let wins = games.iter().filter(|game| game.winner == seat).count();
+                Estimate::proportion(wins, games.len())
+            })
+            .collect();
+        let rounds: Vec<_> = games.iter().map(|game| game.rounds as f64).collect();
+
+        Self {
+            entrants,
+            seat_win_rates,
+            rounds: Estimate::mean(&rounds),
+            unfinished: games
+                .iter()
+                .filter(|game| !game.finished && game.forfeit.is_none())
+                .count(),
+            games,
+        }
+    }
+}
+
+/// Writes the statistics as a table, with the confidence intervals in brackets.
+impl fmt::Display for TournamentReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = |x: &Estimate| {
+            format!(
+                "{:5.1}% [{:.1}-{:.1}]",
+                100.0 * x.mean,
+                100.0 * x.low,
+                100.0 * x.high
+            )
+        };
+        let number = |x: &Estimate| format!("{:5.1} [{:.1}-{:.1}]", x.mean, x.low, x.high);
+
+        writeln!(
+            f,
+            "{} games, {} rounds on average",
+            self.games.len(),
+            number(&self.rounds).trim_start()
+        )?;
+        if self.unfinished > 0 {
+            writeln!(f, "{} games stopped at the round limit", self.unfinished)?;
+        }
+        for (idx, game) in self.games.iter().enumerate() {
+            if let Some(forfeit) = &game.forfeit {
+                writeln!(
+                    f,
+                    "Game {} (seed {}): {} forfeits, it {}",
+                    idx + 1,
+                    game.seed,
+                    self.entrants[game.seats[forfeit.seat]].name,
+                    forfeit.reason
+                )?;
+            }
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<16} {:>6} {:>6} {:>8}  {:<22} Average score",
+            "Entrant", "Games", "Wins", "Forfeits", "Win rate"
+        )?;
+        for entrant in &self.entrants {
+            writeln!(
+                f,
+                "{:<16} {:>6} {:>6} {:>8}  {:<22} {}",
+                entrant.name,
+                entrant.games,
+                entrant.wins,
+                entrant.forfeits,
+                percent(&entrant.win_rate),
+                number(&entrant.score)
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<6} Win rate", "Seat")?;
+        for (seat, win_rate) in self.seat_win_rates.iter().enumerate() {
+            writeln!(f, "{:<6} {}", seat + 1, percent(win_rate))?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays a tournament in which every entrant takes a seat in every game, and reports the statistics.
+///
+/// Each deal, drawn from the master seed, is played once from every rotation of the seats, so that every entrant
+/// plays every seat equally often and with the same cards. The number of games is therefore rounded up to a multiple
+/// of the number of entrants. The games are spread over several threads, but the results only depend on the
+/// configuration.
+///
+/// # Examples
+///
+/// ```
+/// use libcsr::{bots::{greedy::GreedyBot, random::RandomBot}, tournament::{self, Entrant, TournamentConfig}};
+/// let entrants = [
+///     Entrant::new("greedy", |_| Box::new(GreedyBot::default())),
+///     Entrant::new("random", |seed| Box::new(RandomBot::new(seed))),
+/// ];
+/// let config = TournamentConfig { games: 3, seed: 7, ..TournamentConfig::default() };
+/// let report = tournament::run(&entrants, &config).unwrap();
+/// assert_eq!(report.games.len(), 4);
+/// assert_eq!(report.entrants[0].games, 4);
+/// assert_eq!(report.games[0].seats, [0, 1]);
+/// assert_eq!(report.games[1].seats, [1, 0]);
+/// assert_eq!(report.games[0].seed, report.games[1].seed);
+/// ```
+///
+/// # Errors
+///
+/// * `GameErrors::InvalidNumberOfPlayers` if there are not between [`MIN_PLAYERS`] and [`MAX_PLAYERS`] entrants.
+/// * Any error returned by a bot, or by [`Game::play`] for an illegal action, in the first game where one occurs,
+///   except for `GameErrors::BotForfeit`: a bot that forfeits loses its game, see [`GameResult::forfeit`].
+pub fn run(
+    entrants: &[Entrant],
+    config: &TournamentConfig,
+) -> Result<TournamentReport, GameErrors> {
+    let players = entrants.len();
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+        return Err(GameErrors::InvalidNumberOfPlayers(players));
+    }
+
+    let total = config.games.next_multiple_of(players);
+    let mut rng = Rng::new(config.seed);
+    let deals: Vec<_> = (0..total / players).map(|_| rng.next_u64()).collect();
+    let threads = match config.threads {
+        0 => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        threads => threads,
+    };
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(total));
+    thread::scope(|scope| {
+        for _ in 0..threads.min(total) {
+            scope.spawn(|| loop {
+                let game = next.fetch_add(1, Ordering::Relaxed);
+                if game >= total {
+                    break;
+                }
+                let seats: Vec<_> = (0..players).map(|seat| (seat + game) % players).collect();
+                let result = play_game(entrants, seats, deals[game / players], config.max_rounds);
+                let failed = result.is_err();
+                if let Ok(mut results) = results.lock() {
+                    results.push((game, result));
+                }
+                if failed {
+                    // Stop handing out games; the ones already started still finish.
+                    next.store(total, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    let mut results = results
+        .into_inner()
+        .map_err(|_| GameErrors::InternalLogicError)?;
+    results.sort_by_key(|(game, _)| *game);
+    let games = results
+        .into_iter()
+        .map(|(_, result)| result)
+        .collect::<Result<Vec<_>, _>>()?;
+    if games.len() != total {
+        return Err(GameErrors::InternalLogicError);
+    }
+
+    let names: Vec<_> = entrants
+        .iter()
+        .map(|entrant| entrant.name.clone())
+        .collect();
+    Ok(TournamentReport::new(&names, games))
+}
+
+/// Plays one game with the entrants at the given seats, stopping it after `max_rounds` rounds or at a forfeit.
+fn play_game(
+    entrants: &[Entrant],
+    seats: Vec<usize>,
+    seed: u64,
+    max_rounds: usize,
+) -> Result<GameResult, GameErrors> {
+    let mut game = Game::new(seats.len(), seed)?;
+    let mut bots: Vec<_> = seats
+        .iter()
+        .enumerate()
+        .map(|(seat, entrant)| (entrants[*entrant].factory)(seed.wrapping_add(seat as u64)))
+        .collect();
+
+    let mut turns = 0;
+    let mut forfeit = None;
+    // Past the round limit, only the discard of an overflowing last turn is finished, so that the caravans hold
+    // what they can.
+    while !game.is_over()
+        && (turns < max_rounds * seats.len()
+            || game.get_current_player().get_caravan().is_overflowing())
+    {
+        let seat = game.get_current_player_index();
+        let action = match bots[seat].choose_action(&game.view()) {
+            Err(GameErrors::BotForfeit(_, reason)) => {
+                forfeit = Some(Forfeit { seat, reason });
+                break;
+            }
+            action => action?,
+        };
+        if !matches!(action, PlayerAction::Discard(_)) {
+            turns += 1;
+        }
+        game.play(action)?;
+    }
+
+    let mut result = GameResult {
+        seed,
+        scores: game
+            .get_players()
+            .iter()
+            .map(|player| player.score())
+            .collect(),
+        winner: 0,
+        rounds: turns.div_ceil(seats.len()),
+        finished: game.is_over(),
+        forfeit,
+        seats,
+    };
+    result.winner = result.ranking()[0];
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bots::external::{ExternalBot, DEFAULT_TIME_LIMIT};
+    use crate::bots::{greedy::GreedyBot, random::RandomBot};
+    use crate::errors::GameErrors;
+    use crate::tournament::{self, Entrant, TournamentConfig};
+
+    fn entrants() -> Vec<Entrant> {
+        vec![
+            Entrant::new("greedy", |_| Box::new(GreedyBot::default())),
+            Entrant::new("random", |seed| Box::new(RandomBot::new(seed))),
+        ]
+    }
+
+    #[test]
+    fn test_reproducible_across_threads() {
+        let config = TournamentConfig {
+            games: 12,
+            seed: 3,
+            threads: 1,
+            ..TournamentConfig::default()
+        };
+        let report = tournament::run(&entrants(), &config).unwrap();
+        let parallel = TournamentConfig {
+            threads: 4,
+            ..config
+        };
+        assert_eq!(tournament::run(&entrants(), &parallel), Ok(report.clone()));
+
+        assert!(report.entrants[0].win_rate.mean > 0.8, "{report}");
+        assert!(report.entrants[0].score.mean > report.entrants[1].score.mean);
+        assert_eq!(report.entrants[0].wins + report.entrants[1].wins, 12);
+        assert!(
+            report.rounds.low <= report.rounds.mean && report.rounds.mean <= report.rounds.high
+        );
+        assert_eq!(report.unfinished, 0);
+    }
+
+    #[test]
+    fn test_every_seat_equally_often() {
+        let mut entrants = entrants();
+        entrants.push(Entrant::new("random 2", |seed| {
+            Box::new(RandomBot::new(seed))
+        }));
+        let config = TournamentConfig {
+            games: 4,
+            max_rounds: 2,
+            ..TournamentConfig::default()
+        };
+        let report = tournament::run(&entrants, &config).unwrap();
+        assert_eq!(report.games.len(), 6);
+        for entrant in 0..3 {
+            for seat in 0..3 {
+                let games = report
+                    .games
+                    .iter()
+                    .filter(|game| game.seats[seat] == entrant)
+                    .count();
+                assert_eq!(games, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_limit_and_errors() {
+        let config = TournamentConfig {
+            games: 2,
+            max_rounds: 3,
+            ..TournamentConfig::default()
+        };
+        let report = tournament::run(&entrants(), &config).unwrap();
+        assert_eq!(report.unfinished, 2);
+        assert!(report.games.iter().all(|game| game.rounds == 3));
+
+        let result = tournament::run(&entrants()[..1], &config);
+        assert_eq!(result, Err(GameErrors::InvalidNumberOfPlayers(1)));
+
+        let mut entrants = entrants();
+        entrants[1] = Entrant::new("missing", |_| {
+            Box::new(ExternalBot::new(
+                "/nonexistent/bot",
+                &[],
+                DEFAULT_TIME_LIMIT,
+            ))
+        });
+        let report = tournament::run(&entrants, &config).unwrap();
+        assert_eq!(
+            (report.entrants[0].wins, report.entrants[1].forfeits),
+            (2, 2)
+        );
+        assert_eq!(report.unfinished, 0);
+        assert!(report
+            .to_string()
+            .contains("missing forfeits, it cannot start"));
+    }
+}
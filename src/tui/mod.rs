@@ -0,0 +1,675 @@
+use libcsr::cards::{ActionCard, CardChoice, PointsCard};
+use libcsr::game::{Coin, Game};
+use libcsr::player::{PlayerAction, MAX_CARAVAN_SIZE};
+use libcsr::spice::{SpiceAmount, SpiceCube};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, List, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+
+/// Width of a card drawn in one of the rows.
+const CARD_WIDTH: u16 = 14;
+
+/// Runs a hot-seat game in a full-screen terminal interface until the players quit, and returns the game as it was
+/// left.
+pub fn run(game: Game) -> io::Result<Game> {
+    let mut terminal = ratatui::init();
+    let result = App::new(game).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The rows of cards the selection moves between, from top to bottom.
+enum Row {
+    Points,
+    Merchant,
+    Hand,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mode {
+    /// Moving between cards.
+    Browse,
+    /// Picking one of several ways to play the selected card.
+    Choose(Vec<PlayerAction>, usize),
+    /// Picking the caravan cubes an `Upgrade` card applies to, with the steps given to each cube.
+    Upgrade(ActionCard, u8, Vec<u8>),
+    /// Picking the caravan cubes to discard after an overflow.
+    Discard(Vec<bool>),
+}
+
+struct App {
+    game: Game,
+    /// Games before each move, for undo.
+    undo: Vec<Game>,
+    log: Vec<String>,
+    /// Number of lines the log is scrolled up from its end.
+    log_scroll: usize,
+    row: Row,
+    /// Selected card in each row, see [`Row`].
+    selected: [usize; 3],
+    /// Selected cube in the caravan while upgrading or discarding.
+    cube: usize,
+    mode: Mode,
+    message: String,
+    quit: bool,
+}
+
+impl App {
+    fn new(game: Game) -> Self {
+        let mut app = Self {
+            game,
+            undo: Vec::new(),
+            log: Vec::new(),
+            log_scroll: 0,
+            row: Row::Hand,
+            selected: [0; 3],
+            cube: 0,
+            mode: Mode::Browse,
+            message: String::new(),
+            quit: false,
+        };
+        app.reset_mode();
+        app
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<Game> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+        Ok(self.game)
+    }
+
+    fn cubes(&self) -> Vec<SpiceCube> {
+        self.game
+            .get_current_player()
+            .get_caravan()
+            .get_spice_amount()
+            .cubes()
+            .collect()
+    }
+
+    fn row_len(&self, row: Row) -> usize {
+        match row {
+            Row::Points => self.game.get_points_row().len(),
+            Row::Merchant => self.game.get_merchant_row().len(),
+            Row::Hand => self.game.get_current_player().get_hand().len(),
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::PageUp => self.log_scroll = (self.log_scroll + 1).min(self.log.len()),
+            KeyCode::PageDown => self.log_scroll = self.log_scroll.saturating_sub(1),
+            _ => match self.mode.clone() {
+                Mode::Browse => self.browse(code),
+                Mode::Choose(options, selected) => match code {
+                    KeyCode::Up => self.mode = Mode::Choose(options, selected.saturating_sub(1)),
+                    KeyCode::Down => {
+                        let selected = (selected + 1).min(options.len() - 1);
+                        self.mode = Mode::Choose(options, selected);
+                    }
+                    KeyCode::Enter | KeyCode::Char(' ') => self.play(options[selected].clone()),
+                    KeyCode::Esc => self.reset_mode(),
+                    _ => {}
+                },
+                Mode::Upgrade(card, budget, mut steps) => {
+                    match code {
+                        KeyCode::Left => self.cube = self.cube.saturating_sub(1),
+                        KeyCode::Right => self.cube = (self.cube + 1).min(steps.len() - 1),
+                        KeyCode::Up | KeyCode::Char('+') if steps.iter().sum::<u8>() < budget => {
+                            steps[self.cube] += 1
+                        }
+                        KeyCode::Down | KeyCode::Char('-') => {
+                            steps[self.cube] = steps[self.cube].saturating_sub(1)
+                        }
+                        KeyCode::Enter => {
+                            let plan = self
+                                .cubes()
+                                .into_iter()
+                                .zip(steps)
+                                .filter(|(_, steps)| *steps > 0)
+                                .collect();
+                            self.play(PlayerAction::PlayCard(card, CardChoice::Upgrade(plan)));
+                            return;
+                        }
+                        KeyCode::Esc => {
+                            self.reset_mode();
+                            return;
+                        }
+                        _ => {}
+                    }
+                    self.mode = Mode::Upgrade(card, budget, steps);
+                }
+                Mode::Discard(mut marked) => {
+                    match code {
+                        KeyCode::Left => self.cube = self.cube.saturating_sub(1),
+                        KeyCode::Right => self.cube = (self.cube + 1).min(marked.len() - 1),
+                        KeyCode::Char(' ') => marked[self.cube] = !marked[self.cube],
+                        KeyCode::Enter => {
+                            let spices = self
+                                .cubes()
+                                .into_iter()
+                                .zip(marked)
+                                .filter(|(_, marked)| *marked)
+                                .fold(SpiceAmount::default(), |spices, (cube, _)| {
+                                    spices.add(&SpiceAmount::from(cube))
+                                });
+                            self.play(PlayerAction::Discard(spices));
+                            return;
+                        }
+                        _ => {}
+                    }
+                    self.mode = Mode::Discard(marked);
+                }
+            },
+        }
+    }
+
+    fn browse(&mut self, code: KeyCode) {
+        let idx = self.row as usize;
+        match code {
+            KeyCode::Up | KeyCode::BackTab => {
+                self.row = match self.row {
+                    Row::Points | Row::Merchant => Row::Points,
+                    Row::Hand => Row::Merchant,
+                }
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.row = match self.row {
+                    Row::Points => Row::Merchant,
+                    Row::Merchant | Row::Hand => Row::Hand,
+                }
+            }
+            KeyCode::Left => self.selected[idx] = self.selected[idx].saturating_sub(1),
+            KeyCode::Right => {
+                self.selected[idx] = (self.selected[idx] + 1).min(self.row_len(self.row).max(1) - 1)
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => self.activate(),
+            KeyCode::Char('r') => self.play(PlayerAction::Rest),
+            _ => {}
+        }
+    }
+
+    /// Plays the selected card, asking how when there are several ways to.
+    fn activate(&mut self) {
+        let selected = self.selected[self.row as usize];
+        let actions = self.game.legal_actions();
+        let options: Vec<_> = match self.row {
+            Row::Points => {
+                let Some(card) = self.game.get_points_row().get(selected) else {
+                    return;
+                };
+                actions
+                    .into_iter()
+                    .filter(|action| matches!(action, PlayerAction::Score(x) if x == card))
+                    .collect()
+            }
+            Row::Merchant => {
+                let Some(slot) = self.game.get_merchant_row().get(selected) else {
+                    return;
+                };
+                actions
+                    .into_iter()
+                    .filter(|action| matches!(action, PlayerAction::AcquireCard(x, _) if *x == slot.card))
+                    .collect()
+            }
+            Row::Hand => {
+                let Some(card) = self.game.get_current_player().get_hand().get(selected) else {
+                    return;
+                };
+                let options: Vec<_> = actions
+                    .into_iter()
+                    .filter(|action| matches!(action, PlayerAction::PlayCard(x, _) if x == card))
+                    .collect();
+                if let (ActionCard::Upgrade(budget), false) = (card, options.is_empty()) {
+                    self.cube = 0;
+                    self.mode = Mode::Upgrade(*card, *budget, vec![0; self.cubes().len()]);
+                    self.message =
+                        format!("Give up to {budget} upgrade step(s) to the caravan's cubes");
+                    return;
+                }
+                options
+            }
+        };
+
+        match &options[..] {
+            [] => self.message = "That card cannot be played right now".to_string(),
+            [action] => self.play(action.clone()),
+            _ => self.mode = Mode::Choose(options, 0),
+        }
+    }
+
+    fn play(&mut self, action: PlayerAction) {
+        let previous = self.game.clone();
+        let player = self.game.get_current_player_index() + 1;
+        match self.game.play(action.clone()) {
+            Ok(()) => {
+                self.undo.push(previous);
+                self.log.push(format!("Player {player}: {action}"));
+                self.log_scroll = 0;
+                self.message.clear();
+                self.reset_mode();
+            }
+            Err(error) => self.message = error.to_string(),
+        }
+    }
+
+    fn undo(&mut self) {
+        match self.undo.pop() {
+            Some(game) => {
+                self.game = game;
+                self.log.pop();
+                self.message = "Undid the last move".to_string();
+                self.reset_mode();
+            }
+            None => self.message = "Nothing to undo".to_string(),
+        }
+    }
+
+    /// Goes back to browsing the cards, unless the current player has to discard.
+    fn reset_mode(&mut self) {
+        for row in [Row::Points, Row::Merchant, Row::Hand] {
+            let len = self.row_len(row);
+            self.selected[row as usize] = self.selected[row as usize].min(len.max(1) - 1);
+        }
+        self.cube = 0;
+        let caravan = self.game.get_current_player().get_caravan();
+        if caravan.is_overflowing() {
+            self.mode = Mode::Discard(vec![false; self.cubes().len()]);
+            self.message = format!("Choose {} cube(s) to discard", caravan.excess());
+        } else {
+            self.mode = Mode::Browse;
+        }
+        if self.game.is_over() {
+            self.message = "The game is over".to_string();
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [points, merchant, middle, caravan, hand, footer] = Layout::vertical([
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Min(6),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let [players, log] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(middle);
+
+        self.draw_points_row(frame, points);
+        self.draw_merchant_row(frame, merchant);
+        self.draw_players(frame, players);
+        self.draw_log(frame, log);
+        self.draw_caravan(frame, caravan);
+        self.draw_hand(frame, hand);
+        self.draw_footer(frame, footer);
+
+        if let Mode::Choose(options, selected) = &self.mode {
+            let area = popup(frame.area(), 40, options.len() as u16 + 2);
+            let list = List::new(options.iter().map(ToString::to_string))
+                .block(Block::bordered().title(" Choose a move "))
+                .highlight_style(Style::new().reversed());
+            frame.render_widget(Clear, area);
+            frame.render_stateful_widget(
+                list,
+                area,
+                &mut ListState::default().with_selected(Some(*selected)),
+            );
+        }
+        if self.game.is_over() {
+            self.draw_standings(frame);
+        }
+    }
+
+    /// Draws a row of cards, each from its lines, keeping the selected card in view.
+    fn draw_cards(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        row: Row,
+        title: String,
+        cards: Vec<Vec<Line>>,
+    ) {
+        let block = Block::bordered().title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let fit = (inner.width / CARD_WIDTH).max(1) as usize;
+        let selected = self.selected[row as usize];
+        let first = (selected + 1).saturating_sub(fit);
+        let areas = Layout::horizontal(vec![Constraint::Length(CARD_WIDTH); fit]).split(inner);
+        for ((idx, lines), area) in cards.into_iter().enumerate().skip(first).zip(areas.iter()) {
+            let style = if self.row == row && selected == idx && self.mode == Mode::Browse {
+                Style::new().yellow().add_modifier(Modifier::BOLD)
+            } else {
+                Style::new()
+            };
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().border_style(style)),
+                *area,
+            );
+        }
+    }
+
+    fn draw_points_row(&self, frame: &mut Frame, area: Rect) {
+        let coins = self.game.get_coins();
+        let cards = self
+            .game
+            .get_points_row()
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| {
+                let mut lines = points_card_lines(card);
+                match coins.coin_for_slot(idx) {
+                    Some(Coin::Gold) => lines.push(Line::from("● gold".yellow())),
+                    Some(Coin::Silver) => lines.push(Line::from("● silver".gray())),
+                    None => {}
+                }
+                lines
+            })
+            .collect();
+        let title = format!(
+            " Points cards ({} in deck, {} gold and {} silver coins left) ",
+            self.game.points_deck_len(),
+            coins.gold,
+            coins.silver
+        );
+        self.draw_cards(frame, area, Row::Points, title, cards);
+    }
+
+    fn draw_merchant_row(&self, frame: &mut Frame, area: Rect) {
+        let cards = self
+            .game
+            .get_merchant_row()
+            .iter()
+            .enumerate()
+            .map(|(idx, slot)| {
+                let mut lines = action_card_lines(&slot.card);
+                let mut deposit = vec![Span::raw(format!("cost {idx} "))];
+                if slot.spices.total() > 0 {
+                    deposit.push(Span::raw("+"));
+                    deposit.extend(spans(&slot.spices));
+                }
+                lines.push(Line::from(deposit));
+                lines
+            })
+            .collect();
+        let title = format!(
+            " Merchant cards ({} in deck) ",
+            self.game.merchant_deck_len()
+        );
+        self.draw_cards(frame, area, Row::Merchant, title, cards);
+    }
+
+    fn draw_hand(&self, frame: &mut Frame, area: Rect) {
+        let player = self.game.get_current_player();
+        let cards = player.get_hand().iter().map(action_card_lines).collect();
+        let discard = player
+            .get_discard_pile()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let title = format!(" Hand (discard pile: {discard}) ");
+        self.draw_cards(frame, area, Row::Hand, title, cards);
+    }
+
+    fn draw_caravan(&self, frame: &mut Frame, area: Rect) {
+        let cubes = self.cubes();
+        let mut slots = Vec::new();
+        let mut marks = Vec::new();
+        for idx in 0..cubes.len().max(MAX_CARAVAN_SIZE) {
+            if idx == MAX_CARAVAN_SIZE {
+                slots.push(Span::raw("| "));
+                marks.push(Span::raw("  "));
+            }
+            slots.push(Span::raw("["));
+            slots.push(
+                cubes
+                    .get(idx)
+                    .map_or(Span::raw(" "), |cube| cube_span(*cube)),
+            );
+            slots.push(Span::raw("] "));
+            let mark = match &self.mode {
+                Mode::Upgrade(_, _, steps) if idx < steps.len() && steps[idx] > 0 => {
+                    format!("+{:<3}", steps[idx])
+                }
+                Mode::Discard(marked) if idx < marked.len() && marked[idx] => " x  ".to_string(),
+                _ => "    ".to_string(),
+            };
+            let style = match &self.mode {
+                Mode::Upgrade(..) | Mode::Discard(_) if idx == self.cube => Style::new().reversed(),
+                _ => Style::new(),
+            };
+            marks.push(Span::styled(mark, style));
+        }
+        let title = format!(
+            " Caravan of player {} ",
+            self.game.get_current_player_index() + 1
+        );
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(slots), Line::from(marks)])
+                .block(Block::bordered().title(title)),
+            area,
+        );
+    }
+
+    fn draw_players(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<_> = self
+            .game
+            .get_players()
+            .iter()
+            .enumerate()
+            .map(|(idx, player)| {
+                let marker = if idx == self.game.get_current_player_index() {
+                    "▶ "
+                } else {
+                    "  "
+                };
+                let mut line = vec![Span::raw(format!("{marker}Player {} ", idx + 1))];
+                line.extend(spans(&player.get_caravan().get_spice_amount()));
+                line.push(Span::raw(format!(
+                    "  cards {} coins {} score {}",
+                    player.get_score_pile().len(),
+                    player.get_coins().len(),
+                    player.score()
+                )));
+                Line::from(line)
+            })
+            .collect();
+        let title = if self.game.is_end_triggered() {
+            " Players (last round) "
+        } else {
+            " Players "
+        };
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(title)),
+            area,
+        );
+    }
+
+    fn draw_log(&self, frame: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2) as usize;
+        let end = self.log.len() - self.log_scroll;
+        let lines: Vec<_> = self.log[end.saturating_sub(height)..end]
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Log (PgUp/PgDn to scroll) ")),
+            area,
+        );
+    }
+
+    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
+        let keys = match self.mode {
+            Mode::Browse => "←→ select card  ↑↓ change row  Enter play  r rest  u undo  q quit",
+            Mode::Choose(..) => "↑↓ select move  Enter play  Esc cancel  u undo  q quit",
+            Mode::Upgrade(..) => {
+                "←→ select cube  ↑↓ add/remove step  Enter upgrade  Esc cancel  q quit"
+            }
+            Mode::Discard(_) => "←→ select cube  Space mark  Enter discard  u undo  q quit",
+        };
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(keys.dark_gray()),
+                Line::from(self.message.as_str().bold()),
+            ]),
+            area,
+        );
+    }
+
+    fn draw_standings(&self, frame: &mut Frame) {
+        let lines: Vec<_> = self
+            .game
+            .standings()
+            .iter()
+            .map(|standing| {
+                Line::from(format!(
+                    "{}. Player {}: {} points",
+                    standing.rank,
+                    standing.player + 1,
+                    standing.total
+                ))
+            })
+            .collect();
+        let area = popup(frame.area(), 30, lines.len() as u16 + 2);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Final standings ")),
+            area,
+        );
+    }
+}
+
+/// Get a centered area of the given size.
+fn popup(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+fn cube_style(cube: SpiceCube) -> Style {
+    Style::new().fg(match cube {
+        SpiceCube::Turmeric => Color::Yellow,
+        SpiceCube::Saffron => Color::Red,
+        SpiceCube::Cardamon => Color::Green,
+        SpiceCube::Cinnamon => Color::Rgb(139, 69, 19),
+    })
+}
+
+fn cube_span(cube: SpiceCube) -> Span<'static> {
+    Span::styled("■", cube_style(cube))
+}
+
+fn spans(spices: &SpiceAmount) -> Vec<Span<'static>> {
+    if spices.total() == 0 {
+        return vec![Span::raw("-")];
+    }
+    spices.cubes().map(cube_span).collect()
+}
+
+fn action_card_lines(card: &ActionCard) -> Vec<Line<'static>> {
+    match card {
+        ActionCard::Gain(output) => vec![Line::from("gain"), Line::from(spans(output))],
+        ActionCard::Exchange(input, output) => {
+            let mut line = spans(input);
+            line.push(Span::raw(" → "));
+            line.extend(spans(output));
+            vec![Line::from("exchange"), Line::from(line)]
+        }
+        ActionCard::Upgrade(steps) => vec![
+            Line::from("upgrade"),
+            Line::from(format!("{steps} step(s)")),
+        ],
+    }
+}
+
+fn points_card_lines(card: &PointsCard) -> Vec<Line<'static>> {
+    vec![
+        Line::from(format!("{} points", card.points).bold()),
+        Line::from(spans(&card.cost)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, Mode, Row};
+    use libcsr::cards::ActionCard;
+    use libcsr::game::Game;
+    use libcsr::spice_amount;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyCode;
+    use ratatui::Terminal;
+
+    #[test]
+    fn test_play_and_undo() {
+        let mut app = App::new(Game::new(2, 7).unwrap());
+        app.handle_key(KeyCode::Enter);
+        assert_eq!(app.game.get_history().len(), 1);
+        assert_eq!(app.log, ["Player 1: play YY"]);
+
+        app.handle_key(KeyCode::Char('u'));
+        assert_eq!(app.game, Game::new(2, 7).unwrap());
+        assert!(app.log.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_targets() {
+        let mut app = App::new(Game::new(2, 7).unwrap());
+        app.handle_key(KeyCode::Right);
+        app.handle_key(KeyCode::Enter);
+        assert_eq!(
+            app.mode,
+            Mode::Upgrade(ActionCard::Upgrade(2), 2, vec![0; 3])
+        );
+
+        for key in [
+            KeyCode::Up,
+            KeyCode::Right,
+            KeyCode::Up,
+            KeyCode::Up,
+            KeyCode::Enter,
+        ] {
+            app.handle_key(key);
+        }
+        assert_eq!(app.mode, Mode::Browse);
+        assert_eq!(app.row, Row::Hand);
+        assert_eq!(
+            app.game.get_players()[0].get_caravan().get_spice_amount(),
+            spice_amount!(1, 2, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_draw_any_size() {
+        let mut app = App::new(Game::new(5, 3).unwrap());
+        for _ in 0..40 {
+            let actions = app.game.legal_actions();
+            app.play(actions[actions.len() / 2].clone());
+        }
+        for (width, height) in [(120, 40), (60, 20), (10, 5)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(|frame| app.draw(frame)).unwrap();
+        }
+    }
+}